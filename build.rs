@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    example_tests();
+    input_tests();
+}
+
+// The subdirectories of `dir` named `<prefix><number>`, with their number.
+fn numbered(dir: &Path, prefix: &str) -> Vec<(u32, PathBuf)> {
+    let mut found = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let number = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix(prefix)?
+                .parse()
+                .ok()?;
+            Some((number, path))
+        })
+        .collect::<Vec<_>>();
+    found.sort();
+    found
+}
+
+// Writes one test per test_inputs/YYYY/dayNN/<name>.txt that has a
+// <name>.part1 or <name>.part2 file with the expected answer next to it. The
+// tests are included by src/examples.rs.
+fn example_tests() {
+    println!("cargo:rerun-if-changed=test_inputs");
+    let mut code = String::new();
+    for (year, year_dir) in numbered(Path::new("test_inputs"), "") {
+        for (day, dir) in numbered(&year_dir, "day") {
+            let mut inputs = fs::read_dir(&dir)
+                .unwrap()
+                .flatten()
+                .map(|e| e.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect::<Vec<_>>();
+            inputs.sort();
+            for input in inputs {
+                for part in 1..=2 {
+                    let sidecar = input.with_extension(format!("part{}", part));
+                    let Ok(expected) = fs::read_to_string(&sidecar) else {
+                        continue;
+                    };
+                    let stem = input.file_stem().unwrap().to_string_lossy();
+                    let name = format!("year{}_day{:02}_{}_part{}", year, day, stem, part)
+                        .chars()
+                        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                        .collect::<String>();
+                    code.push_str(&format!(
+                        "#[test]\nfn {}() {{\n    super::check({}, {}, {}, {:?}, {:?});\n}}\n\n",
+                        name,
+                        year,
+                        day,
+                        part,
+                        input.to_string_lossy().replace('\\', "/"),
+                        expected.trim()
+                    ));
+                }
+            }
+        }
+    }
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("example_tests.rs");
+    fs::write(out, code).unwrap();
+}
+
+// Writes one ignored test per src/yearYYYY/dayNN.rs that checks the answers
+// for the real input. The tests are included by src/answers.rs.
+fn input_tests() {
+    println!("cargo:rerun-if-changed=src");
+    let mut code = String::new();
+    for (year, dir) in numbered(Path::new("src"), "year") {
+        let mut days = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                name.strip_prefix("day")?.strip_suffix(".rs")?.parse().ok()
+            })
+            .collect::<Vec<u32>>();
+        days.sort();
+        for day in days {
+            code.push_str(&format!(
+                "#[test]\n#[ignore = \"needs input/{0}/day{1:02}/input.txt\"]\nfn year{0}_day{1:02}_input() {{\n    super::check_input({0}, {1});\n}}\n\n",
+                year, day
+            ));
+        }
+    }
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("input_tests.rs");
+    fs::write(out, code).unwrap();
+}
@@ -0,0 +1,206 @@
+use std::path::{Path, PathBuf};
+
+use crate::parse::ParseError;
+use crate::solution::Answer;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Unknown,
+    // The part never produced an answer to check.
+    Panicked(String),
+    TimedOut,
+    Invalid(ParseError),
+    // The input could not be read or downloaded.
+    NoInput(String),
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Status::Pass => "pass",
+            Status::Fail(_) => "FAIL",
+            Status::Unknown => "?",
+            Status::Panicked(_) => "FAILED",
+            Status::TimedOut => "TIMEOUT",
+            Status::Invalid(_) => "INVALID",
+            Status::NoInput(_) => "NO INPUT",
+        };
+    }
+
+    pub fn is_error(&self) -> bool {
+        return matches!(
+            self,
+            Status::Panicked(_) | Status::TimedOut | Status::Invalid(_) | Status::NoInput(_)
+        );
+    }
+}
+
+pub fn path(year: u32, day: u32) -> PathBuf {
+    return PathBuf::from(format!("answers/{}/day{:02}.txt", year, day));
+}
+
+// One answer per line, part 1 first. An empty line leaves that part unknown.
+pub fn parse_answers(contents: &str) -> [Option<String>; 2] {
+    let mut lines = contents.lines().map(|line| {
+        let line = line.trim();
+        if line.is_empty() {
+            None
+        } else {
+            Some(line.to_string())
+        }
+    });
+    return [lines.next().flatten(), lines.next().flatten()];
+}
+
+pub fn load(year: u32, day: u32) -> [Option<String>; 2] {
+    return match std::fs::read_to_string(path(year, day)) {
+        Ok(contents) => parse_answers(&contents),
+        Err(_) => [None, None],
+    };
+}
+
+// Stores `answer` as the expected answer of `part`, keeping the other part.
+pub fn record(year: u32, day: u32, part: u32, answer: &str) -> std::io::Result<()> {
+    let mut answers = load(year, day);
+    answers[part as usize - 1] = Some(answer.to_string());
+    let path = path(year, day);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let [first, second] = answers.map(Option::unwrap_or_default);
+    return std::fs::write(path, format!("{}\n{}\n", first, second));
+}
+
+// The recorded answers are for the cached input at `cached`, so a file passed
+// with --input is only checked against them when it is that same file.
+pub fn applies(input: Option<&str>, cached: &Path) -> bool {
+    let Some(input) = input else {
+        return true;
+    };
+    if input == "-" {
+        return false;
+    }
+    return match (std::fs::canonicalize(input), std::fs::canonicalize(cached)) {
+        (Ok(input), Ok(cached)) => input == cached,
+        _ => false,
+    };
+}
+
+pub fn check(expected: &Option<String>, answer: &Answer) -> Status {
+    return match (expected, answer) {
+        (None, _) => Status::Unknown,
+        (Some(expected), Answer::Unsolved) => Status::Fail(expected.clone()),
+        (Some(expected), answer) if *expected == answer.to_string() => Status::Pass,
+        (Some(expected), _) => Status::Fail(expected.clone()),
+    };
+}
+
+// Runs both parts of `year` `day` on its cached input and compares them with
+// the recorded answers. Days without an input or answers are skipped, and
+// nothing is downloaded.
+#[cfg(test)]
+fn check_input(year: u32, day: u32) {
+    let path = crate::input::path(std::path::Path::new("input"), year, day);
+    let Ok(input) = std::fs::read_to_string(&path) else {
+        eprintln!("skipping {} day {}: no {}", year, day, path.display());
+        return;
+    };
+    let expected = load(year, day);
+    if expected == [None, None] {
+        eprintln!(
+            "skipping {} day {}: no answers in {}",
+            year,
+            day,
+            self::path(year, day).display()
+        );
+        return;
+    }
+    let solution = crate::solutions()
+        .into_iter()
+        .find(|s| s.year() == year && s.day() == day)
+        .unwrap_or_else(|| panic!("no solution for {} day {}", year, day));
+    let parsed = match solution.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(mut error) => {
+            error.file = Some(path.display().to_string());
+            panic!("\n{}", error.snippet());
+        }
+    };
+    for (part, expected) in (1..=2).zip(expected) {
+        let Some(expected) = expected else {
+            eprintln!("skipping {} day {} part {}: no answer", year, day, part);
+            continue;
+        };
+        let answer = match part {
+            1 => solution.part1(parsed.as_ref()),
+            _ => solution.part2(parsed.as_ref()),
+        };
+        assert_eq!(
+            answer.to_string(),
+            expected,
+            "{} part {}",
+            path.display(),
+            part
+        );
+    }
+}
+
+// One ignored test per day, see build.rs. Run them with
+// `cargo test -- --ignored`.
+#[cfg(test)]
+mod input_tests {
+    include!(concat!(env!("OUT_DIR"), "/input_tests.rs"));
+}
+
+#[cfg(test)]
+mod answers_tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_answers("142\n281\n"),
+            [Some("142".to_string()), Some("281".to_string())]
+        );
+        assert_eq!(parse_answers("\n281\n"), [None, Some("281".to_string())]);
+        assert_eq!(parse_answers("54\n"), [Some("54".to_string()), None]);
+        assert_eq!(parse_answers(""), [None, None]);
+    }
+
+    #[test]
+    fn status() {
+        let expected = Some("288".to_string());
+        assert_eq!(check(&expected, &Answer::Integer(288)), Status::Pass);
+        assert_eq!(
+            check(&expected, &Answer::Integer(289)),
+            Status::Fail("288".to_string())
+        );
+        assert_eq!(
+            check(&expected, &Answer::Unsolved),
+            Status::Fail("288".to_string())
+        );
+        assert_eq!(check(&None, &Answer::Integer(288)), Status::Unknown);
+        assert_eq!(check(&None, &Answer::Unsolved), Status::Unknown);
+    }
+
+    #[test]
+    fn only_for_the_cached_input() {
+        let root = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let cached = crate::input::path(&root, 2023, 6);
+        std::fs::create_dir_all(cached.parent().unwrap()).unwrap();
+        std::fs::write(&cached, "Time: 7\n").unwrap();
+        let example = root.join("test01.txt");
+        std::fs::write(&example, "Time: 7\n").unwrap();
+        let detour = root.join("2023/day06/../day06/input.txt");
+
+        assert!(applies(None, &cached));
+        assert!(applies(cached.to_str(), &cached));
+        assert!(applies(detour.to_str(), &cached));
+        assert!(!applies(example.to_str(), &cached));
+        assert!(!applies(Some("-"), &cached));
+        assert!(!applies(root.join("missing.txt").to_str(), &cached));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+
+use crate::report::Record;
+
+// Changes smaller than this are noise no matter what the relative threshold says.
+const NOISE_FLOOR_NS: f64 = 1000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Entry {
+    pub parse: f64,
+    pub solve: f64,
+}
+
+pub type Baseline = BTreeMap<(u32, u32), Entry>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+    New,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub parse: f64,
+    pub solve: f64,
+    pub verdict: Verdict,
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let marker = match self.verdict {
+            Verdict::Regressed => "▲",
+            Verdict::Improved => "▼",
+            Verdict::Unchanged => "=",
+            Verdict::New => return write!(f, "new"),
+        };
+        write!(
+            f,
+            "{:+.0}% / {:+.0}% {}",
+            self.parse * 100.0,
+            self.solve * 100.0,
+            marker
+        )
+    }
+}
+
+pub fn path(year: u32, name: &str) -> PathBuf {
+    return PathBuf::from(format!("benchmarks/{}/{}.tsv", year, name));
+}
+
+pub fn parse_baseline(contents: &str) -> Baseline {
+    let mut baseline = Baseline::new();
+    for line in contents.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let toks = line.split('\t').collect::<Vec<_>>();
+        if toks.len() != 4 {
+            continue;
+        }
+        let (Ok(day), Ok(part), Ok(parse), Ok(solve)) = (
+            toks[0].parse::<u32>(),
+            toks[1].parse::<u32>(),
+            toks[2].parse::<f64>(),
+            toks[3].parse::<f64>(),
+        ) else {
+            continue;
+        };
+        baseline.insert((day, part), Entry { parse, solve });
+    }
+    return baseline;
+}
+
+pub fn format_baseline(baseline: &Baseline) -> String {
+    let mut contents = String::from("# day\tpart\tparse_median_ns\tsolve_median_ns\n");
+    for ((day, part), entry) in baseline.iter() {
+        contents.push_str(&format!(
+            "{}\t{}\t{}\t{}\n",
+            day, part, entry.parse, entry.solve
+        ));
+    }
+    return contents;
+}
+
+pub fn load(year: u32, name: &str) -> io::Result<Baseline> {
+    return Ok(parse_baseline(&std::fs::read_to_string(path(year, name))?));
+}
+
+// Entries for days that were not part of this run are kept.
+pub fn save(year: u32, name: &str, records: &[Record]) -> io::Result<()> {
+    let path = path(year, name);
+    let mut baseline = match std::fs::read_to_string(&path) {
+        Ok(contents) => parse_baseline(&contents),
+        Err(_) => Baseline::new(),
+    };
+    for record in records {
+        baseline.insert(
+            (record.day, record.part),
+            Entry {
+                parse: record.parse.median,
+                solve: record.solve.median,
+            },
+        );
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    return std::fs::write(path, format_baseline(&baseline));
+}
+
+fn change(current: f64, baseline: f64, threshold: f64) -> (f64, Verdict) {
+    let ratio = if baseline > 0.0 {
+        current / baseline - 1.0
+    } else {
+        0.0
+    };
+    if (current - baseline).abs() < NOISE_FLOOR_NS || ratio.abs() <= threshold {
+        return (ratio, Verdict::Unchanged);
+    }
+    if ratio > 0.0 {
+        return (ratio, Verdict::Regressed);
+    }
+    return (ratio, Verdict::Improved);
+}
+
+pub fn compare(baseline: &Baseline, record: &Record, threshold: f64) -> Comparison {
+    let Some(entry) = baseline.get(&(record.day, record.part)) else {
+        return Comparison {
+            parse: 0.0,
+            solve: 0.0,
+            verdict: Verdict::New,
+        };
+    };
+    let (parse, parse_verdict) = change(record.parse.median, entry.parse, threshold);
+    let (solve, solve_verdict) = change(record.solve.median, entry.solve, threshold);
+    let verdict = match (parse_verdict, solve_verdict) {
+        (Verdict::Regressed, _) | (_, Verdict::Regressed) => Verdict::Regressed,
+        (Verdict::Improved, _) | (_, Verdict::Improved) => Verdict::Improved,
+        _ => Verdict::Unchanged,
+    };
+    return Comparison {
+        parse,
+        solve,
+        verdict,
+    };
+}
+
+#[cfg(test)]
+mod baseline_tests {
+    use super::*;
+    use crate::solution::Answer;
+    use crate::utils::Stats;
+
+    fn record(parse: f64, solve: f64) -> Record {
+        return Record {
+            parse: Stats::from_samples(&[parse]),
+            solve: Stats::from_samples(&[solve]),
+            ..Record::new(2023, 12, 2, Answer::Unsolved)
+        };
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut baseline = Baseline::new();
+        baseline.insert(
+            (12, 2),
+            Entry {
+                parse: 1500.0,
+                solve: 117667000.0,
+            },
+        );
+        baseline.insert(
+            (1, 1),
+            Entry {
+                parse: 20.5,
+                solve: 28000.0,
+            },
+        );
+        let contents = format_baseline(&baseline);
+        assert!(contents.starts_with("# day\tpart"));
+        assert_eq!(parse_baseline(&contents), baseline);
+    }
+
+    #[test]
+    fn verdicts() {
+        let mut baseline = Baseline::new();
+        baseline.insert(
+            (12, 2),
+            Entry {
+                parse: 50000.0,
+                solve: 100000.0,
+            },
+        );
+        let verdict = |parse, solve| compare(&baseline, &record(parse, solve), 0.1).verdict;
+        assert_eq!(verdict(50000.0, 105000.0), Verdict::Unchanged);
+        assert_eq!(verdict(50000.0, 120000.0), Verdict::Regressed);
+        assert_eq!(verdict(80000.0, 50000.0), Verdict::Regressed);
+        assert_eq!(verdict(50000.0, 50000.0), Verdict::Improved);
+        assert_eq!(
+            compare(&Baseline::new(), &record(1.0, 1.0), 0.1).verdict,
+            Verdict::New
+        );
+
+        let comparison = compare(&baseline, &record(50000.0, 150000.0), 0.1);
+        assert_eq!(comparison.to_string(), "+0% / +50% ▲");
+    }
+
+    #[test]
+    fn noise_floor() {
+        let mut baseline = Baseline::new();
+        baseline.insert(
+            (12, 2),
+            Entry {
+                parse: 100.0,
+                solve: 200.0,
+            },
+        );
+        let comparison = compare(&baseline, &record(300.0, 600.0), 0.1);
+        assert_eq!(comparison.verdict, Verdict::Unchanged);
+    }
+}
@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::input::{Input, InputSource};
+
+const USER_AGENT: &str = concat!(
+    "aoc2023-rs/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/cranil/aoc2023-rs)"
+);
+const ATTEMPTS: u32 = 3;
+const BACKOFF: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub enum DownloadError {
+    // The session cookie is missing, expired or belongs to nobody.
+    Unauthorized(String),
+    NotYetUnlocked { year: u32, day: u32 },
+    // The server could not be reached or kept failing after every retry.
+    Network(String),
+    Io(std::io::Error),
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadError::Unauthorized(reason) => write!(f, "unauthorized: {}", reason),
+            DownloadError::NotYetUnlocked { year, day } => {
+                write!(f, "{} day {} is not unlocked yet", year, day)
+            }
+            DownloadError::Network(reason) => write!(f, "network error: {}", reason),
+            DownloadError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for DownloadError {
+    fn from(e: std::io::Error) -> Self {
+        return DownloadError::Io(e);
+    }
+}
+
+// Puzzle inputs are plain text. Anything that looks like a web page is the
+// site telling us something went wrong, even when it comes back with a 200.
+pub fn is_error_page(body: &str) -> bool {
+    let start = body.trim_start().to_ascii_lowercase();
+    return start.starts_with("<!doctype html")
+        || start.starts_with("<html")
+        || body.starts_with("Puzzle inputs differ by user.");
+}
+
+// Whether a failed attempt is worth retrying, and what to report if not.
+fn classify(status: StatusCode, body: &str, year: u32, day: u32) -> (bool, DownloadError) {
+    return match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (
+            false,
+            DownloadError::Unauthorized(format!("the server answered {}", status)),
+        ),
+        StatusCode::NOT_FOUND => (false, DownloadError::NotYetUnlocked { year, day }),
+        StatusCode::OK => (
+            false,
+            DownloadError::Unauthorized(
+                "the server sent a web page instead of an input".to_string(),
+            ),
+        ),
+        _ if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS => (
+            true,
+            DownloadError::Network(format!("the server answered {}", status)),
+        ),
+        _ => (
+            false,
+            DownloadError::Network(format!("unexpected answer {}: {}", status, body.trim())),
+        ),
+    };
+}
+
+// A session token and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub token: String,
+    pub source: String,
+}
+
+// Accepts either a bare token or whitespace separated `key=value` pairs with a
+// `session` entry, which is what the browser's cookie header looks like.
+fn parse_session(contents: &str) -> Result<String, String> {
+    let contents = contents.trim();
+    if contents.is_empty() {
+        return Err("empty".to_string());
+    }
+    if !contents.contains(|c: char| c == '=' || c.is_whitespace()) {
+        return Ok(contents.to_string());
+    }
+    let pairs = contents
+        .split_whitespace()
+        .filter_map(|token| token.split_once('='))
+        .collect::<HashMap<_, _>>();
+    return match pairs.get("session") {
+        Some(session) if !session.is_empty() => Ok(session.trim_end_matches(';').to_string()),
+        _ => Err("no session=... entry".to_string()),
+    };
+}
+
+fn read_session(path: &Path) -> Result<String, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    return parse_session(&contents);
+}
+
+// $XDG_CONFIG_HOME/aoc2023/session, falling back to ~/.config.
+fn config_file() -> Option<PathBuf> {
+    let dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    return Some(dir.join("aoc2023").join("session"));
+}
+
+// The first source with a usable token wins. The message says why each source
+// before it was skipped, or why all of them were.
+fn choose(candidates: Vec<(String, Result<String, String>)>) -> Result<(Session, String), String> {
+    let mut skipped = Vec::new();
+    for (source, token) in candidates {
+        match token {
+            Ok(token) => return Ok((Session { token, source }, skipped.join("; "))),
+            Err(reason) => skipped.push(format!("{}: {}", source, reason)),
+        }
+    }
+    return Err(format!("no session token found ({})", skipped.join("; ")));
+}
+
+// Looks for a token in $AOC_SESSION, then `flag`, then cookies/session, then
+// the per-user config file.
+pub fn find_session(flag: Option<&str>) -> Result<Session, DownloadError> {
+    let config = match config_file() {
+        Some(path) => (path.display().to_string(), read_session(&path)),
+        None => (
+            "~/.config/aoc2023/session".to_string(),
+            Err("neither XDG_CONFIG_HOME nor HOME is set".to_string()),
+        ),
+    };
+    let candidates = vec![
+        (
+            "$AOC_SESSION".to_string(),
+            std::env::var("AOC_SESSION")
+                .map_err(|_| "not set".to_string())
+                .and_then(|token| parse_session(&token)),
+        ),
+        (
+            "--session".to_string(),
+            flag.ok_or("not given".to_string()).and_then(parse_session),
+        ),
+        (
+            "cookies/session".to_string(),
+            read_session(Path::new("cookies/session")),
+        ),
+        config,
+    ];
+    let (session, skipped) = choose(candidates).map_err(DownloadError::Unauthorized)?;
+    if skipped.is_empty() {
+        eprintln!("Using the session token from {}", session.source);
+    } else {
+        eprintln!(
+            "Using the session token from {} ({})",
+            session.source, skipped
+        );
+    }
+    return Ok(session);
+}
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+// Downloads inputs from `base_url`, retrying server errors with a doubling
+// `backoff`. `session` is the --session flag, if any. The token is looked up
+// on the first request and kept for the rest.
+pub struct Http {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub backoff: Duration,
+    found: OnceLock<Result<Session, String>>,
+}
+
+impl Http {
+    pub fn new(session: Option<String>) -> Http {
+        return Http {
+            base_url: BASE_URL.to_string(),
+            session,
+            backoff: BACKOFF,
+            found: OnceLock::new(),
+        };
+    }
+
+    fn session(&self) -> Result<&Session, DownloadError> {
+        let found = self.found.get_or_init(|| {
+            return find_session(self.session.as_deref()).map_err(|e| match e {
+                DownloadError::Unauthorized(reason) => reason,
+                e => e.to_string(),
+            });
+        });
+        return found
+            .as_ref()
+            .map_err(|reason| DownloadError::Unauthorized(reason.clone()));
+    }
+}
+
+// What is being asked of the site. Inputs need a session and must not be web
+// pages. Puzzle pages only need a session to show part two. Answers are
+// posted once, never retried, since every attempt counts.
+enum Request<'a> {
+    Input,
+    Page,
+    Answer { part: u32, answer: &'a str },
+}
+
+impl Http {
+    fn send(&self, path: &str, year: u32, day: u32, kind: Request) -> Result<Input, DownloadError> {
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), path);
+        let session = match self.session() {
+            Ok(session) => Some(session),
+            Err(_) if matches!(kind, Request::Page) => None,
+            Err(e) => return Err(e),
+        };
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| DownloadError::Network(e.to_string()))?;
+        let attempts = match kind {
+            Request::Answer { .. } => 1,
+            _ => ATTEMPTS,
+        };
+
+        let mut backoff = self.backoff;
+        let mut attempt = 1;
+        loop {
+            let mut request = match kind {
+                Request::Answer { part, answer } => client
+                    .post(&url)
+                    .form(&[("level", part.to_string()), ("answer", answer.to_string())]),
+                _ => client.get(&url),
+            };
+            if let Some(session) = &session {
+                request = request.header("Cookie", format!("session={}", session.token));
+            }
+            let response = request
+                .send()
+                .and_then(|res| Ok((res.status(), res.text()?)));
+            let (retry, error) = match response {
+                Ok((status, body))
+                    if status == StatusCode::OK
+                        && (!matches!(kind, Request::Input) || !is_error_page(&body)) =>
+                {
+                    return Ok(Input {
+                        origin: url,
+                        contents: body,
+                    });
+                }
+                Ok((status, body)) => match classify(status, &body, year, day) {
+                    (retry, DownloadError::Unauthorized(reason)) => (
+                        retry,
+                        DownloadError::Unauthorized(match &session {
+                            Some(session) => {
+                                format!("{} for the session token from {}", reason, session.source)
+                            }
+                            None => reason,
+                        }),
+                    ),
+                    classified => classified,
+                },
+                Err(e) => (true, DownloadError::Network(e.to_string())),
+            };
+            if !retry || attempt >= attempts {
+                return Err(error);
+            }
+            std::thread::sleep(backoff);
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+
+    // The puzzle description, as HTML.
+    pub fn page(&self, year: u32, day: u32) -> Result<Input, DownloadError> {
+        return self.send(&format!("/{}/day/{}", year, day), year, day, Request::Page);
+    }
+
+    // Posts `answer` for `part` and returns the page the site answers with.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<Input, DownloadError> {
+        let path = format!("/{}/day/{}/answer", year, day);
+        return self.send(&path, year, day, Request::Answer { part, answer });
+    }
+}
+
+impl InputSource for Http {
+    fn input(&self, year: u32, day: u32) -> Result<Input, DownloadError> {
+        return self.send(
+            &format!("/{}/day/{}/input", year, day),
+            year,
+            day,
+            Request::Input,
+        );
+    }
+}
+
+#[cfg(test)]
+mod download_tests {
+    use super::*;
+    use crate::input::Cache;
+
+    #[test]
+    fn error_pages() {
+        assert!(is_error_page("<!DOCTYPE html>\n<html lang=\"en-us\">"));
+        assert!(is_error_page("  <html><body>500</body></html>"));
+        assert!(is_error_page(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ));
+        assert!(!is_error_page("Time:        44     82     69     81\n"));
+    }
+
+    #[test]
+    fn statuses() {
+        let check = |status| classify(status, "", 2023, 6);
+        assert!(matches!(
+            check(StatusCode::BAD_REQUEST),
+            (false, DownloadError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            check(StatusCode::NOT_FOUND),
+            (false, DownloadError::NotYetUnlocked { year: 2023, day: 6 })
+        ));
+        assert!(matches!(
+            check(StatusCode::SERVICE_UNAVAILABLE),
+            (true, DownloadError::Network(_))
+        ));
+        assert!(matches!(
+            check(StatusCode::TOO_MANY_REQUESTS),
+            (true, DownloadError::Network(_))
+        ));
+        assert!(matches!(
+            check(StatusCode::IM_A_TEAPOT),
+            (false, DownloadError::Network(_))
+        ));
+    }
+
+    #[test]
+    fn sessions() {
+        assert_eq!(
+            parse_session("53616c7465645f5f\n"),
+            Ok("53616c7465645f5f".to_string())
+        );
+        assert_eq!(
+            parse_session("_ga=GA1.2 session=53616c7465645f5f; _gid=GA1.2"),
+            Ok("53616c7465645f5f".to_string())
+        );
+        assert!(parse_session("_ga=GA1.2 _gid=GA1.2").is_err());
+        assert!(parse_session("  \n").is_err());
+
+        let (session, skipped) = choose(vec![
+            ("$AOC_SESSION".to_string(), Err("not set".to_string())),
+            ("--session".to_string(), Ok("abc".to_string())),
+            ("cookies/session".to_string(), Ok("def".to_string())),
+        ])
+        .unwrap();
+        assert_eq!(session.token, "abc");
+        assert_eq!(session.source, "--session");
+        assert_eq!(skipped, "$AOC_SESSION: not set");
+
+        let error = choose(vec![
+            ("$AOC_SESSION".to_string(), Err("not set".to_string())),
+            ("--session".to_string(), Err("not given".to_string())),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "no session token found ($AOC_SESSION: not set; --session: not given)"
+        );
+    }
+
+    // Answers each connection on 127.0.0.1 with the next canned response and
+    // hands back the requests it saw.
+    fn serve(responses: Vec<String>) -> (Http, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let http = Http {
+            base_url: format!("http://{}", listener.local_addr().unwrap()),
+            backoff: Duration::ZERO,
+            ..Http::new(Some("53616c7465645f5f".to_string()))
+        };
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                // The headers, then as much body as they announce.
+                let complete = |request: &[u8]| {
+                    let text = String::from_utf8_lossy(request).to_lowercase();
+                    let Some(end) = text.find("\r\n\r\n") else {
+                        return false;
+                    };
+                    let length = text[..end]
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |n| n.trim().parse::<usize>().unwrap());
+                    return request.len() >= end + 4 + length;
+                };
+                while !complete(&request) {
+                    let n = stream.read(&mut buffer).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..n]);
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(String::from_utf8_lossy(&request).to_string());
+            }
+            return requests;
+        });
+        return (http, server);
+    }
+
+    fn response(status: &str, body: &str) -> String {
+        return format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+    }
+
+    #[test]
+    fn download() {
+        let (http, server) = serve(vec![response("200 OK", "Time: 7\nDistance: 9\n")]);
+        let input = http.input(2023, 6).unwrap();
+        assert_eq!(input.contents, "Time: 7\nDistance: 9\n");
+        assert_eq!(input.origin, format!("{}/2023/day/6/input", http.base_url));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2023/day/6/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("cookie: session="));
+        assert!(requests[0].contains(&format!("user-agent: {}", USER_AGENT)));
+
+        // The token is looked up once per client.
+        let found = http.found.get().unwrap().as_ref().unwrap();
+        assert!(std::ptr::eq(http.session().unwrap(), found));
+    }
+
+    #[test]
+    fn pages() {
+        let page = "<!DOCTYPE html>\n<html><pre><code>Time: 7\n</code></pre></html>\n";
+        let (http, server) = serve(vec![response("200 OK", page)]);
+        assert_eq!(http.page(2023, 6).unwrap().contents, page);
+        assert!(server.join().unwrap()[0].starts_with("GET /2023/day/6 HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn answers() {
+        let page = "<html><main><article><p>That's the right answer!</p></article></main></html>";
+        let (http, server) = serve(vec![
+            response("200 OK", page),
+            response("500 Internal Server Error", ""),
+            response("200 OK", page),
+        ]);
+        assert_eq!(http.submit(2023, 6, 2, "71503").unwrap().contents, page);
+        // Posting again is never automatic, even when the server fails.
+        assert!(matches!(
+            http.submit(2023, 6, 2, "71503"),
+            Err(DownloadError::Network(_))
+        ));
+        assert!(http.submit(2023, 6, 2, "71503").is_ok());
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/6/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("content-type: application/x-www-form-urlencoded"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=71503"));
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[test]
+    fn retries() {
+        let (http, server) = serve(vec![
+            response("503 Service Unavailable", ""),
+            response("200 OK", "Time: 7\n"),
+        ]);
+        assert_eq!(http.input(2023, 6).unwrap().contents, "Time: 7\n");
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        let (http, server) = serve(vec![response("500 Internal Server Error", ""); 3]);
+        assert!(matches!(
+            http.input(2023, 6),
+            Err(DownloadError::Network(_))
+        ));
+        assert_eq!(server.join().unwrap().len(), ATTEMPTS as usize);
+    }
+
+    #[test]
+    fn failures() {
+        let (http, server) = serve(vec![response("404 Not Found", "")]);
+        assert!(matches!(
+            http.input(2023, 26),
+            Err(DownloadError::NotYetUnlocked {
+                year: 2023,
+                day: 26
+            })
+        ));
+        server.join().unwrap();
+
+        // An error page must never end up in the cache.
+        let root = std::env::temp_dir().join(format!("aoc-download-{}", std::process::id()));
+        let (http, server) = serve(vec![response(
+            "200 OK",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+        let cache = Cache {
+            root: root.clone(),
+            upstream: http,
+        };
+        assert!(matches!(
+            cache.input(2023, 6),
+            Err(DownloadError::Unauthorized(_))
+        ));
+        server.join().unwrap();
+        assert!(!crate::input::path(&root, 2023, 6).exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,199 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    return text;
+}
+
+pub fn unescape(text: &str) -> String {
+    // &amp; goes last so "&amp;lt;" stays "&lt;".
+    return text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+}
+
+// The text of every `<pre><code>` block on a puzzle page, in page order and
+// without repeats. Highlighting like `<em>` is dropped.
+pub fn extract(html: &str) -> Vec<String> {
+    let (open, close) = ("<pre><code>", "</code></pre>");
+    let mut blocks: Vec<String> = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        let mut block = unescape(&strip_tags(&after[..end]));
+        if !block.ends_with('\n') {
+            block.push('\n');
+        }
+        if !block.trim().is_empty() && !blocks.contains(&block) {
+            blocks.push(block);
+        }
+        rest = &after[end + close.len()..];
+    }
+    return blocks;
+}
+
+// Where the examples of `year` `day` are kept, see build.rs.
+pub fn dir(year: u32, day: u32) -> PathBuf {
+    return PathBuf::from(format!("test_inputs/{}/day{:02}", year, day));
+}
+
+// The number of a testNN.txt file name.
+fn test_number(path: &Path) -> Option<u32> {
+    let name = path.file_name()?.to_str()?;
+    return name
+        .strip_prefix("test")?
+        .strip_suffix(".txt")?
+        .parse()
+        .ok();
+}
+
+// Writes the blocks that are not in `dir` yet as the next free testNN.txt
+// files. Returns the file of every block and whether it was just written.
+pub fn save(dir: &Path, blocks: &[String]) -> io::Result<Vec<(PathBuf, bool)>> {
+    std::fs::create_dir_all(dir)?;
+    let mut existing = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(number) = test_number(&path) {
+            let contents = std::fs::read_to_string(&path)?;
+            existing.push((number, path, contents));
+        }
+    }
+    let mut next = existing.iter().map(|(n, _, _)| *n).max().unwrap_or(0) + 1;
+    let mut saved = Vec::new();
+    for block in blocks {
+        if let Some((_, path, _)) = existing.iter().find(|(_, _, c)| c == block) {
+            saved.push((path.clone(), false));
+            continue;
+        }
+        let path = dir.join(format!("test{:02}.txt", next));
+        std::fs::write(&path, block)?;
+        existing.push((next, path.clone(), block.clone()));
+        saved.push((path, true));
+        next += 1;
+    }
+    return Ok(saved);
+}
+
+// A one-line description to pick the examples by.
+pub fn summary(block: &str) -> String {
+    let first = block.lines().next().unwrap_or("");
+    let first = if first.chars().count() > 40 {
+        format!("{}…", first.chars().take(39).collect::<String>())
+    } else {
+        first.to_string()
+    };
+    return format!("{:>3} line(s)  {}", block.lines().count(), first);
+}
+
+// Runs `part` of the solution for `year` `day` on the example at `path`.
+#[cfg(test)]
+fn check(year: u32, day: u32, part: u32, path: &str, expected: &str) {
+    let solution = crate::solutions()
+        .into_iter()
+        .find(|s| s.year() == year && s.day() == day)
+        .unwrap_or_else(|| panic!("no solution for {} day {}", year, day));
+    let input = crate::utils::read_all(path).unwrap();
+    let parsed = match solution.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(mut error) => {
+            error.file = Some(path.to_string());
+            panic!("\n{}", error.snippet());
+        }
+    };
+    let answer = match part {
+        1 => solution.part1(parsed.as_ref()),
+        _ => solution.part2(parsed.as_ref()),
+    };
+    assert_eq!(answer.to_string(), expected, "{} part {}", path, part);
+}
+
+// One test per example with a .part1 or .part2 answer file, see build.rs.
+#[cfg(test)]
+mod example_tests {
+    include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+}
+
+#[cfg(test)]
+mod examples_tests {
+    use super::*;
+
+    #[test]
+    fn pages() {
+        let page = std::fs::read_to_string("test_inputs/pages/2023-day06.html").unwrap();
+        let blocks = extract(&page);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            blocks[0],
+            std::fs::read_to_string("test_inputs/2023/day06/test01.txt").unwrap()
+        );
+        assert_eq!(blocks[1], "Time:      71530\nDistance:  940200\n");
+
+        // Highlighted values inside an example keep their text.
+        let page = std::fs::read_to_string("test_inputs/pages/2023-day09.html").unwrap();
+        let blocks = extract(&page);
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(
+            blocks[0],
+            std::fs::read_to_string("test_inputs/2023/day09/test01.txt").unwrap()
+        );
+        assert_eq!(
+            blocks[3],
+            "0   3   6   9  12  15  18\n  3   3   3   3   3   3\n    0   0   0   0   0\n"
+        );
+
+        // Inline `<code>` in the text is not an example.
+        let page = std::fs::read_to_string("test_inputs/pages/2023-day19.html").unwrap();
+        assert_eq!(
+            extract(&page),
+            [std::fs::read_to_string("test_inputs/2023/day19/test01.txt").unwrap()]
+        );
+    }
+
+    #[test]
+    fn saving() {
+        assert_eq!(dir(2024, 5), PathBuf::from("test_inputs/2024/day05"));
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("test01.txt"), "1abc2\n").unwrap();
+        std::fs::write(dir.join("test03.txt"), "two1nine\n").unwrap();
+
+        let blocks = vec!["two1nine\n".to_string(), "pqr3stu8vwx\n".to_string()];
+        let saved = save(&dir, &blocks).unwrap();
+        assert_eq!(
+            saved,
+            vec![
+                (dir.join("test03.txt"), false),
+                (dir.join("test04.txt"), true)
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("test04.txt")).unwrap(),
+            "pqr3stu8vwx\n"
+        );
+        assert_eq!(
+            save(&dir, &blocks).unwrap()[1],
+            (dir.join("test04.txt"), false)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(summary("Time: 7\nDistance: 9\n"), "  2 line(s)  Time: 7");
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::download::{self, DownloadError};
+use crate::utils;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    // A path or URL, used when pointing at the input in messages.
+    pub origin: String,
+    pub contents: String,
+}
+
+pub trait InputSource: Sync {
+    fn input(&self, year: u32, day: u32) -> Result<Input, DownloadError>;
+}
+
+pub fn path(root: &Path, year: u32, day: u32) -> PathBuf {
+    return root
+        .join(year.to_string())
+        .join(format!("day{:02}", day))
+        .join("input.txt");
+}
+
+// Recorded next to each cached input, as `key=value` lines in input.meta.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meta {
+    pub checksum: u64,
+    pub downloaded: u64,
+    pub bytes: u64,
+    pub trailing_newline: bool,
+}
+
+// 64-bit FNV-1a. Only meant to notice that a file changed.
+pub fn checksum(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    return hash;
+}
+
+pub fn meta_path(path: &Path) -> PathBuf {
+    return path.with_extension("meta");
+}
+
+impl Meta {
+    pub fn of(contents: &str, downloaded: u64) -> Meta {
+        return Meta {
+            checksum: checksum(contents.as_bytes()),
+            downloaded,
+            bytes: contents.len() as u64,
+            trailing_newline: contents.ends_with('\n'),
+        };
+    }
+
+    pub fn parse(text: &str) -> Option<Meta> {
+        let pairs = text
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .collect::<HashMap<_, _>>();
+        return Some(Meta {
+            checksum: u64::from_str_radix(pairs.get("fnv1a64")?, 16).ok()?,
+            downloaded: pairs.get("downloaded")?.parse().ok()?,
+            bytes: pairs.get("bytes")?.parse().ok()?,
+            trailing_newline: pairs.get("trailing_newline")?.parse().ok()?,
+        });
+    }
+
+    pub fn to_text(&self) -> String {
+        return format!(
+            "fnv1a64={:016x}\ndownloaded={}\nbytes={}\ntrailing_newline={}\n",
+            self.checksum, self.downloaded, self.bytes, self.trailing_newline
+        );
+    }
+}
+
+// What is wrong with a cached input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Missing,
+    Unreadable(String),
+    Empty,
+    Html,
+    // Put there by hand, or cached before metadata was recorded.
+    Unrecorded,
+    Changed(Vec<String>),
+}
+
+impl Problem {
+    // Inputs that cannot be right and are downloaded again.
+    pub fn is_corrupt(&self) -> bool {
+        return matches!(self, Problem::Empty | Problem::Html);
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Missing => write!(f, "missing"),
+            Problem::Unreadable(e) => write!(f, "unreadable: {}", e),
+            Problem::Empty => write!(f, "empty"),
+            Problem::Html => write!(f, "looks like a web page"),
+            Problem::Unrecorded => write!(f, "no metadata"),
+            Problem::Changed(changes) => {
+                write!(f, "changed since download: {}", changes.join(", "))
+            }
+        }
+    }
+}
+
+// Compares `contents` of the input at `path` with what was recorded when it
+// was downloaded.
+pub fn check(path: &Path, contents: &str) -> Result<Meta, Problem> {
+    if contents.trim().is_empty() {
+        return Err(Problem::Empty);
+    }
+    if download::is_error_page(contents) {
+        return Err(Problem::Html);
+    }
+    let recorded = std::fs::read_to_string(meta_path(path)).ok();
+    let Some(recorded) = recorded.as_deref().and_then(Meta::parse) else {
+        return Err(Problem::Unrecorded);
+    };
+    let actual = Meta::of(contents, recorded.downloaded);
+    let mut changes = Vec::new();
+    if actual.bytes != recorded.bytes {
+        changes.push(format!(
+            "{} bytes instead of {}",
+            actual.bytes, recorded.bytes
+        ));
+    }
+    if actual.trailing_newline != recorded.trailing_newline {
+        changes.push(if recorded.trailing_newline {
+            "lost its trailing newline".to_string()
+        } else {
+            "gained a trailing newline".to_string()
+        });
+    }
+    if actual.checksum != recorded.checksum {
+        changes.push(format!(
+            "checksum {:016x} instead of {:016x}",
+            actual.checksum, recorded.checksum
+        ));
+    }
+    if !changes.is_empty() {
+        return Err(Problem::Changed(changes));
+    }
+    return Ok(recorded);
+}
+
+pub fn audit(path: &Path) -> Result<Meta, Problem> {
+    return match std::fs::read_to_string(path) {
+        Ok(contents) => check(path, &contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(Problem::Missing),
+        Err(e) => Err(Problem::Unreadable(e.to_string())),
+    };
+}
+
+// Inputs kept under `root`, asked from `upstream` the first time they are
+// needed. The input is written to a temporary file first so an interrupted
+// download never looks cached.
+pub struct Cache<S: InputSource> {
+    pub root: PathBuf,
+    pub upstream: S,
+}
+
+impl<S: InputSource> InputSource for Cache<S> {
+    fn input(&self, year: u32, day: u32) -> Result<Input, DownloadError> {
+        let path = path(&self.root, year, day);
+        let origin = path.display().to_string();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => match check(&path, &contents) {
+                Err(problem) if problem.is_corrupt() => {
+                    eprintln!("{}: {}, downloading it again", origin, problem);
+                }
+                Err(problem @ Problem::Changed(_)) => {
+                    eprintln!("warning: {}: {}", origin, problem);
+                    return Ok(Input { origin, contents });
+                }
+                _ => return Ok(Input { origin, contents }),
+            },
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            Err(_) => {}
+        }
+        let contents = self.upstream.input(year, day)?.contents;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("part");
+        std::fs::write(&partial, &contents)?;
+        std::fs::rename(&partial, &path)?;
+        let meta = Meta::of(&contents, utils::unix_time());
+        std::fs::write(meta_path(&path), meta.to_text())?;
+        return Ok(Input { origin, contents });
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Memory {
+    pub inputs: HashMap<(u32, u32), String>,
+}
+
+impl InputSource for Memory {
+    fn input(&self, year: u32, day: u32) -> Result<Input, DownloadError> {
+        return match self.inputs.get(&(year, day)) {
+            Some(contents) => Ok(Input {
+                origin: format!("<memory {} day {}>", year, day),
+                contents: contents.clone(),
+            }),
+            None => Err(DownloadError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no input for {} day {}", year, day),
+            ))),
+        };
+    }
+}
+
+#[cfg(test)]
+mod input_tests {
+    use super::*;
+
+    #[test]
+    fn cache() {
+        let root = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let mut upstream = Memory::default();
+        upstream
+            .inputs
+            .insert((2023, 6), "Time: 7\nDistance: 9\n".to_string());
+        let cache = Cache {
+            root: root.clone(),
+            upstream,
+        };
+
+        let input = cache.input(2023, 6).unwrap();
+        assert_eq!(input.origin, path(&root, 2023, 6).display().to_string());
+        assert_eq!(input.contents, "Time: 7\nDistance: 9\n");
+        assert!(path(&root, 2023, 6).exists());
+        assert!(!path(&root, 2023, 6).with_extension("part").exists());
+        assert!(audit(&path(&root, 2023, 6)).is_ok());
+        assert!(cache.input(2023, 7).is_err());
+        assert!(!path(&root, 2023, 7).exists());
+
+        // Once cached the upstream is never asked again.
+        let cache = Cache {
+            root: root.clone(),
+            upstream: Memory::default(),
+        };
+        assert_eq!(cache.input(2023, 6).unwrap(), input);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn integrity() {
+        let root = std::env::temp_dir().join(format!("aoc-integrity-{}", std::process::id()));
+        let file = path(&root, 2023, 6);
+        let mut upstream = Memory::default();
+        upstream.inputs.insert((2023, 6), "Time: 7\n".to_string());
+        let cache = Cache {
+            root: root.clone(),
+            upstream,
+        };
+        assert_eq!(audit(&file), Err(Problem::Missing));
+        let meta = Meta::of("Time: 7\n", 1701846000);
+        assert_eq!(Meta::parse(&meta.to_text()), Some(meta));
+
+        // Corrupt inputs are downloaded again.
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "").unwrap();
+        assert_eq!(audit(&file), Err(Problem::Empty));
+        assert_eq!(cache.input(2023, 6).unwrap().contents, "Time: 7\n");
+        std::fs::write(&file, "<!DOCTYPE html>\n<html>").unwrap();
+        assert_eq!(audit(&file), Err(Problem::Html));
+        assert_eq!(cache.input(2023, 6).unwrap().contents, "Time: 7\n");
+        assert_eq!(audit(&file).unwrap().bytes, 8);
+
+        // Edited ones are only reported.
+        std::fs::write(&file, "Time: 8").unwrap();
+        let Err(Problem::Changed(changes)) = audit(&file) else {
+            panic!("edit not noticed");
+        };
+        assert_eq!(
+            changes[..2],
+            ["7 bytes instead of 8", "lost its trailing newline"]
+        );
+        assert_eq!(cache.input(2023, 6).unwrap().contents, "Time: 8");
+
+        std::fs::remove_file(meta_path(&file)).unwrap();
+        assert_eq!(audit(&file), Err(Problem::Unrecorded));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,239 +1,693 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-
 pub mod algos;
+pub mod answers;
+pub mod baseline;
+pub mod download;
+pub mod examples;
 pub mod grid;
+pub mod input;
+#[cfg_attr(not(feature = "memory"), allow(dead_code))]
+pub mod memory;
+pub mod parse;
+pub mod property;
+pub mod report;
+pub mod scaffold;
+pub mod select;
+pub mod solution;
+pub mod submit;
 pub mod utils;
+pub mod watch;
+pub mod year2023;
+
+pub fn solutions() -> Vec<&'static dyn solution::Solution> {
+    return [year2023::solutions()].concat();
+}
 
 use clap::Parser;
 
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(short, long, default_value_t = 0)]
-    day: u32,
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+// Which days and parts a subcommand applies to. No --days means every day of
+// the year that has a solution.
+#[derive(clap::Args, Debug)]
+struct Selection {
+    #[arg(short, long, default_value_t = 2023)]
+    year: u32,
 
-    #[arg(short, long, default_value_t = 0)]
-    part: u32,
+    #[arg(short, long, alias = "day", value_name = "DAYS", value_parser = select::days)]
+    days: Option<select::Ranges>,
+
+    #[arg(short, long, alias = "part", value_name = "PARTS", value_parser = select::parts)]
+    parts: Option<select::Ranges>,
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    #[arg(short, long, alias = "input-file", value_name = "FILE|-")]
+    input: Option<String>,
+
+    #[arg(short, long, value_enum, default_value_t = report::Format::Table)]
+    format: report::Format,
 
     #[arg(short, long, default_value_t = 1)]
-    num_runs: usize,
+    jobs: usize,
+
+    #[arg(short, long, value_name = "SECONDS")]
+    timeout: Option<f64>,
 
-    #[arg(short, long)]
-    input_file: Option<String>,
+    #[arg(short, long, hide = cfg!(not(feature = "memory")))]
+    memory: bool,
+
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    trace: Option<utils::TraceFormat>,
+
+    #[arg(long, value_name = "FILE", requires = "trace")]
+    trace_file: Option<String>,
+
+    #[arg(long, value_name = "TOKEN")]
+    session: Option<String>,
 }
 
-fn default_input_file(day: u32) -> String {
-    let filename = format!("input/day{:02}/input.txt", day);
-    let filepath = std::path::Path::new(&filename);
-    if filepath.exists() {
-        return filename;
-    } else {
-        let contents = utils::download_input(&day);
-        std::fs::write(&filename, &contents).unwrap();
-        return filename;
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Runs each selected part once and checks it against the answers store.
+    Run {
+        #[command(flatten)]
+        run: RunArgs,
+
+        #[arg(long)]
+        watch: bool,
+
+        #[arg(long, value_name = "DIR", requires = "watch")]
+        watch_dir: Option<String>,
+    },
+    /// Times each selected part over several runs, optionally against a baseline.
+    Bench {
+        #[command(flatten)]
+        run: RunArgs,
+
+        #[arg(short, long, default_value_t = 10)]
+        num_runs: usize,
+
+        #[arg(short, long, default_value_t = 1)]
+        warmup: usize,
+
+        // Baselines are keyed by day and part, so they only hold timings for
+        // the cached real inputs.
+        #[arg(long, conflicts_with = "input")]
+        save_baseline: Option<String>,
+
+        #[arg(long, conflicts_with = "input")]
+        compare: Option<String>,
+
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Like run, but fails when an answer does not match the answers store.
+    Verify {
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Downloads the inputs of the selected days that are not cached yet.
+    Fetch {
+        #[command(flatten)]
+        selection: Selection,
+
+        #[arg(long, value_name = "TOKEN")]
+        session: Option<String>,
+    },
+    /// Saves the example blocks of the selected puzzle pages as test inputs.
+    FetchExamples {
+        #[command(flatten)]
+        selection: Selection,
+
+        #[arg(long, value_name = "TOKEN")]
+        session: Option<String>,
+    },
+    /// Posts the answer of one part, unless it is known to be wrong or too soon.
+    Submit {
+        #[arg(short, long, default_value_t = 2023)]
+        year: u32,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+
+        // Sends this instead of running the solution.
+        #[arg(short, long)]
+        answer: Option<String>,
+
+        #[arg(long, value_name = "TOKEN")]
+        session: Option<String>,
+    },
+    /// Audits the cached inputs of all 25 days against their recorded metadata.
+    CheckInputs {
+        #[arg(short, long, default_value_t = 2023)]
+        year: u32,
+
+        #[arg(long)]
+        refetch: bool,
+
+        #[arg(long, value_name = "TOKEN")]
+        session: Option<String>,
+    },
+    /// Lists the selected days with their title and what is available locally.
+    List {
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// Writes a skeleton dayNN module and registers it with the runner.
+    New {
+        #[arg(short, long, default_value_t = 2023)]
+        year: u32,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        #[arg(long)]
+        title: Option<String>,
+    },
+}
+
+// Inputs live under input/ and are downloaded the first time they are needed.
+fn input_source(session: &Option<String>) -> Box<dyn input::InputSource> {
+    return Box::new(input::Cache {
+        root: std::path::PathBuf::from("input"),
+        upstream: download::Http::new(session.clone()),
+    });
+}
+
+fn select(selection: &Selection) -> Vec<&'static dyn solution::Solution> {
+    let selected = solutions()
+        .into_iter()
+        .filter(|s| s.year() == selection.year)
+        .filter(|s| {
+            selection
+                .days
+                .as_ref()
+                .map_or(true, |d| d.contains(s.day()))
+        })
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        eprintln!("No solutions selected for {}", selection.year);
+        std::process::exit(2);
     }
+    return selected;
+}
+
+// Everything a run needs, whichever subcommand asked for it.
+struct Plan {
+    selected: Vec<&'static dyn solution::Solution>,
+    options: solution::RunOptions,
+    input: Option<String>,
+    format: report::Format,
+    columns: report::Columns,
+    jobs: usize,
+    baseline: Option<baseline::Baseline>,
+    threshold: f64,
+    trace: Option<utils::TraceFormat>,
+    trace_file: Option<String>,
+    source: Box<dyn input::InputSource>,
 }
 
-macro_rules! input_file {
-    ($day:expr, $file:expr) => {
-        match $file {
-            Some(f) => f.clone(),
-            None => default_input_file($day),
+impl Plan {
+    fn new(run: &RunArgs) -> Plan {
+        let selected = select(&run.selection);
+        if run.memory && cfg!(not(feature = "memory")) {
+            eprintln!("--memory needs a build with `--features memory`");
+            std::process::exit(2);
+        }
+        if run.input.is_some() && selected.len() != 1 {
+            eprintln!("--input needs a single day");
+            std::process::exit(2);
         }
-        .as_str()
+        let parts = match &run.selection.parts {
+            Some(parts) => parts.0.clone(),
+            None => vec![1, 2],
+        };
+        return Plan {
+            selected,
+            options: solution::RunOptions {
+                parts,
+                num_runs: 1,
+                warmup: 0,
+                timeout: run.timeout.map(std::time::Duration::from_secs_f64),
+                memory: run.memory,
+                trace: run.trace.is_some(),
+            },
+            input: run.input.clone(),
+            format: run.format,
+            columns: report::Columns {
+                baseline: false,
+                memory: run.memory,
+            },
+            jobs: run.jobs,
+            baseline: None,
+            threshold: 0.0,
+            trace: run.trace,
+            trace_file: run.trace_file.clone(),
+            source: input_source(&run.session),
+        };
+    }
+}
+
+// Runs the selected days once, printing records as they come in.
+fn execute(plan: &Plan) -> Vec<report::Record> {
+    let mut out = std::io::stdout();
+    let mut reporter = plan.format.reporter(plan.columns);
+    let mut records = Vec::new();
+    reporter.header(&mut out).unwrap();
+    let run_day = |solution: &&'static dyn solution::Solution| {
+        let (year, day) = (solution.year(), solution.day());
+        let (input, source) = (&plan.input, &plan.source);
+        let input = match input {
+            Some(f) => utils::read_input(f)
+                .map(|contents| (f.clone(), contents))
+                .map_err(|error| error.to_string()),
+            None => source
+                .input(year, day)
+                .map(|input| (input.origin, input.contents))
+                .map_err(|error| error.to_string()),
+        };
+        let (path, input) = match input {
+            Ok(input) => input,
+            Err(error) => {
+                let status = answers::Status::NoInput(error);
+                return solution::failed(*solution, &plan.options, status);
+            }
+        };
+        let cached = input::path(std::path::Path::new("input"), year, day);
+        let expected = if answers::applies(plan.input.as_deref(), &cached) {
+            answers::load(year, day)
+        } else {
+            [None, None]
+        };
+        let mut records = solution::run(*solution, &plan.options, &input);
+        for record in records.iter_mut() {
+            if let answers::Status::Invalid(error) = &mut record.status {
+                error.file = Some(if path == "-" {
+                    "<stdin>".to_string()
+                } else {
+                    path.clone()
+                });
+            }
+        }
+        for record in records.iter_mut().filter(|r| !r.status.is_error()) {
+            record.status = answers::check(&expected[record.part as usize - 1], &record.answer);
+            if let Some(baseline) = &plan.baseline {
+                record.comparison =
+                    Some(baseline::compare(baseline, record, plan.threshold / 100.0));
+            }
+        }
+        return records;
     };
-    ($day:expr) => {
-        default_input_file($day).as_str()
+    utils::parallel_ordered(&plan.selected, plan.jobs, run_day, |day_records| {
+        for record in day_records {
+            reporter.record(&mut out, &record).unwrap();
+            records.push(record);
+        }
+    });
+    reporter.footer(&mut out).unwrap();
+    // Both parts of a day share the one parse error, show it once.
+    let mut shown = std::collections::BTreeSet::new();
+    for record in records.iter() {
+        if let answers::Status::Invalid(error) = &record.status {
+            if shown.insert((record.year, record.day)) {
+                eprint!("{}", error.snippet());
+            }
+        }
+    }
+    if let Some(format) = plan.trace {
+        dump_spans(format, &plan.trace_file, &records);
+    }
+    return records;
+}
+
+// Spans go to stderr unless --trace-file says otherwise, so the report on
+// stdout stays machine readable.
+fn dump_spans(format: utils::TraceFormat, file: &Option<String>, records: &[report::Record]) {
+    let mut dump = String::new();
+    for record in records.iter().filter(|r| !r.spans.is_empty()) {
+        let prefix = format!("{};day{:02}", record.year, record.day);
+        if format == utils::TraceFormat::Tree {
+            dump.push_str(&format!(
+                "{} day {} part {}\n",
+                record.year, record.day, record.part
+            ));
+        }
+        dump.push_str(&utils::format_spans(format, &prefix, &record.spans));
+    }
+    match file {
+        Some(file) => {
+            if let Err(e) = std::fs::write(file, dump) {
+                eprintln!("Failed to write spans to {}: {}", file, e);
+            }
+        }
+        None => eprint!("{}", dump),
+    }
+}
+
+// Prints what went wrong and exits with 1 if the run should count as failed.
+fn finish(records: &[report::Record], verify: bool) {
+    let regressions = records
+        .iter()
+        .filter(|r| matches!(&r.comparison, Some(c) if c.verdict == baseline::Verdict::Regressed))
+        .count();
+    if regressions > 0 {
+        eprintln!("{} part(s) regressed against the baseline", regressions);
+    }
+    let mismatches = records
+        .iter()
+        .filter(|r| matches!(r.status, answers::Status::Fail(_)))
+        .count();
+    if verify && mismatches > 0 {
+        eprintln!("{} answer(s) do not match the answers store", mismatches);
+    }
+    let errors = records.iter().filter(|r| r.status.is_error()).count();
+    if errors > 0 {
+        eprintln!(
+            "{} part(s) had missing or invalid input, panicked or timed out",
+            errors
+        );
+    }
+    if errors > 0 || regressions > 0 || (verify && mismatches > 0) {
+        std::process::exit(1);
+    }
+}
+
+fn watch(plan: &Plan, watch_dir: &Option<String>) {
+    if plan.selected.len() != 1 || plan.input.as_deref() == Some("-") {
+        eprintln!("--watch needs a single day and an input file");
+        std::process::exit(2);
+    }
+    let input = match &plan.input {
+        Some(f) => f.clone(),
+        None => match plan
+            .source
+            .input(plan.selected[0].year(), plan.selected[0].day())
+        {
+            Ok(input) => input.origin,
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(2);
+            }
+        },
+    };
+    let mut paths = vec![std::path::PathBuf::from(input)];
+    if let Some(dir) = watch_dir {
+        paths.push(std::path::PathBuf::from(dir));
+    }
+    let mut previous: Vec<report::Record> = Vec::new();
+    watch::watch(&paths, watch::POLL_INTERVAL, || {
+        let records = execute(plan);
+        if !previous.is_empty() {
+            for line in watch::diff(&previous, &records) {
+                eprintln!("{}", line);
+            }
+        }
+        previous = records;
+        eprintln!("Watching {} path(s) for changes", paths.len());
+        return true;
+    });
+}
+
+fn submit(year: u32, day: u32, part: u32, answer: &Option<String>, session: &Option<String>) {
+    let answer = match answer {
+        Some(answer) => answer.clone(),
+        None => {
+            let selection = Selection {
+                year,
+                days: Some(select::Ranges(vec![day])),
+                parts: None,
+            };
+            let solution = select(&selection)[0];
+            let input = match input_source(session).input(year, day) {
+                Ok(input) => input,
+                Err(error) => {
+                    eprintln!("{} day {:02}: {}", year, day, error);
+                    std::process::exit(2);
+                }
+            };
+            let options = solution::RunOptions {
+                parts: vec![part],
+                num_runs: 1,
+                warmup: 0,
+                timeout: None,
+                memory: false,
+                trace: false,
+            };
+            let record = solution::run(solution, &options, &input.contents).remove(0);
+            if record.status.is_error() || record.answer == solution::Answer::Unsolved {
+                eprintln!(
+                    "{} day {:02} part {} has no answer to submit",
+                    year, day, part
+                );
+                std::process::exit(2);
+            }
+            record.answer.to_string()
+        }
+    };
+    let now = utils::unix_time();
+    if let Some(reason) = submit::refusal(&submit::load(year, day), part, &answer, now) {
+        eprintln!("Not submitting {}: {}", answer, reason);
+        std::process::exit(1);
+    }
+    let page = match download::Http::new(session.clone()).submit(year, day, part, &answer) {
+        Ok(page) => page,
+        Err(error) => {
+            eprintln!("{} day {:02}: {}", year, day, error);
+            std::process::exit(2);
+        }
+    };
+    let response = submit::parse_response(&page.contents);
+    let submission = submit::Submission {
+        time: now,
+        part,
+        answer: answer.clone(),
+        outcome: response.outcome,
+        wait: response.wait.map_or(0, |wait| wait.as_secs()),
     };
+    if let Err(e) = submit::append(year, day, &submission) {
+        eprintln!("Failed to record the submission: {}", e);
+    }
+    if response.outcome == submit::Outcome::Correct {
+        if let Err(e) = answers::record(year, day, part, &answer) {
+            eprintln!("Failed to store the answer: {}", e);
+        }
+    }
+    println!("{}: {}", response.outcome.name(), response.message);
+    if response.outcome != submit::Outcome::Correct {
+        std::process::exit(1);
+    }
+}
+
+fn check_inputs(year: u32, refetch: bool, session: &Option<String>) {
+    let source = input_source(session);
+    let now = utils::unix_time();
+    let mut bad = 0;
+    for day in 1..=25 {
+        let path = input::path(std::path::Path::new("input"), year, day);
+        let mut result = input::audit(&path);
+        let broken =
+            matches!(&result, Err(p) if p.is_corrupt() || matches!(p, input::Problem::Changed(_)));
+        if refetch && broken {
+            let _ = std::fs::remove_file(&path);
+            let _ = std::fs::remove_file(input::meta_path(&path));
+            result = match source.input(year, day) {
+                Ok(_) => input::audit(&path),
+                Err(e) => Err(input::Problem::Unreadable(e.to_string())),
+            };
+        }
+        let line = match &result {
+            Ok(meta) => {
+                let age = now.saturating_sub(meta.downloaded);
+                format!(
+                    "ok  {} bytes, downloaded {} ago",
+                    meta.bytes,
+                    if age < 2 * 86400 {
+                        format!("{}h", age / 3600)
+                    } else {
+                        format!("{}d", age / 86400)
+                    }
+                )
+            }
+            Err(problem) => problem.to_string(),
+        };
+        println!("{} day {:02}  {}", year, day, line);
+        if let Err(problem) = &result {
+            if !matches!(
+                problem,
+                input::Problem::Missing | input::Problem::Unrecorded
+            ) {
+                bad += 1;
+            }
+        }
+    }
+    if bad > 0 {
+        eprintln!(
+            "{} input(s) need attention, --refetch downloads them again",
+            bad
+        );
+        std::process::exit(1);
+    }
 }
 
 fn main() {
-    let args = Args::parse();
-    let header = format!(
-        "
-┌────┬────────┬────────────────────────────────┬────────────┐
-│{:^4}│{:^8}│{:^32}│{:^12}│",
-        "Day", "Part", "Result", "Time (us)"
-    );
-    println!("{}", header);
-    match args.day {
-        01 => day01::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        02 => day02::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        03 => day03::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        04 => day04::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        05 => day05::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        06 => day06::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        07 => day07::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        08 => day08::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        09 => day09::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        10 => day10::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        11 => day11::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        12 => day12::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        13 => day13::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        14 => day14::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        15 => day15::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        16 => day16::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        17 => day17::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        18 => day18::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        19 => day19::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        20 => day20::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        21 => day21::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        22 => day22::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        23 => day23::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        24 => day24::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        25 => day25::main(
-            &args.part,
-            &args.num_runs,
-            input_file!(args.day, args.input_file),
-        ),
-        _ => {
-            day01::main(&args.part, &args.num_runs, input_file!(01));
-            day02::main(&args.part, &args.num_runs, input_file!(02));
-            day03::main(&args.part, &args.num_runs, input_file!(03));
-            day04::main(&args.part, &args.num_runs, input_file!(04));
-            day05::main(&args.part, &args.num_runs, input_file!(05));
-            day06::main(&args.part, &args.num_runs, input_file!(06));
-            day07::main(&args.part, &args.num_runs, input_file!(07));
-            day08::main(&args.part, &args.num_runs, input_file!(08));
-            day09::main(&args.part, &args.num_runs, input_file!(09));
-            day10::main(&args.part, &args.num_runs, input_file!(10));
-            day11::main(&args.part, &args.num_runs, input_file!(11));
-            day12::main(&args.part, &args.num_runs, input_file!(12));
-            day13::main(&args.part, &args.num_runs, input_file!(13));
-            day14::main(&args.part, &args.num_runs, input_file!(14));
-            day15::main(&args.part, &args.num_runs, input_file!(15));
-            day16::main(&args.part, &args.num_runs, input_file!(16));
-            day17::main(&args.part, &args.num_runs, input_file!(17));
-            day18::main(&args.part, &args.num_runs, input_file!(18));
-            day19::main(&args.part, &args.num_runs, input_file!(19));
-            day20::main(&args.part, &args.num_runs, input_file!(20));
-            day21::main(&args.part, &args.num_runs, input_file!(21));
-            day22::main(&args.part, &args.num_runs, input_file!(22));
-            day23::main(&args.part, &args.num_runs, input_file!(23));
-            day24::main(&args.part, &args.num_runs, input_file!(24));
-            day25::main(&args.part, &args.num_runs, input_file!(25));
-        }
-    }
-    let footer = format!("└────┴────────┴────────────────────────────────┴────────────┘");
-    println!("{}", footer);
+    let cli = Cli::parse();
+    match &cli.command {
+        Command::Run {
+            run,
+            watch: true,
+            watch_dir,
+        } => watch(&Plan::new(run), watch_dir),
+        Command::Run { run, .. } => finish(&execute(&Plan::new(run)), false),
+        Command::Verify { run } => finish(&execute(&Plan::new(run)), true),
+        Command::Bench {
+            run,
+            num_runs,
+            warmup,
+            save_baseline,
+            compare,
+            threshold,
+        } => {
+            let mut plan = Plan::new(run);
+            plan.options.num_runs = *num_runs;
+            plan.options.warmup = *warmup;
+            plan.threshold = *threshold;
+            if let Some(name) = compare {
+                match baseline::load(run.selection.year, name) {
+                    Ok(baseline) => plan.baseline = Some(baseline),
+                    Err(e) => {
+                        eprintln!("Failed to load baseline {}: {}", name, e);
+                        std::process::exit(2);
+                    }
+                }
+            }
+            plan.columns.baseline = plan.baseline.is_some();
+            let records = execute(&plan);
+            if let Some(name) = save_baseline {
+                let completed = records
+                    .iter()
+                    .filter(|r| !r.status.is_error())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if let Err(e) = baseline::save(run.selection.year, name, &completed) {
+                    eprintln!("Failed to save baseline {}: {}", name, e);
+                    std::process::exit(2);
+                }
+            }
+            finish(&records, false);
+        }
+        Command::Fetch { selection, session } => {
+            let source = input_source(session);
+            let mut failed = false;
+            for solution in select(selection) {
+                let (year, day) = (solution.year(), solution.day());
+                match source.input(year, day) {
+                    Ok(input) => println!("{}", input.origin),
+                    Err(error) => {
+                        eprintln!("{} day {:02}: {}", year, day, error);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::FetchExamples { selection, session } => {
+            let http = download::Http::new(session.clone());
+            let mut failed = false;
+            for solution in select(selection) {
+                let (year, day) = (solution.year(), solution.day());
+                let dir = examples::dir(year, day);
+                let blocks = match http.page(year, day) {
+                    Ok(page) => examples::extract(&page.contents),
+                    Err(error) => {
+                        eprintln!("{} day {:02}: {}", year, day, error);
+                        failed = true;
+                        continue;
+                    }
+                };
+                match examples::save(&dir, &blocks) {
+                    Ok(saved) => {
+                        for ((path, new), block) in saved.iter().zip(&blocks) {
+                            println!(
+                                "{}  {:<7}  {}",
+                                path.display(),
+                                if *new { "new" } else { "known" },
+                                examples::summary(block)
+                            );
+                        }
+                        if saved.iter().any(|(_, new)| *new) {
+                            println!(
+                                "Put the expected answers in testNN.part1 and testNN.part2 to test them"
+                            );
+                        }
+                    }
+                    Err(error) => {
+                        eprintln!("{} day {:02}: {}", year, day, error);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            session,
+        } => submit(*year, *day, *part, answer, session),
+        Command::CheckInputs {
+            year,
+            refetch,
+            session,
+        } => check_inputs(*year, *refetch, session),
+        Command::List { selection } => {
+            for solution in select(selection) {
+                let (year, day) = (solution.year(), solution.day());
+                let input = input::path(std::path::Path::new("input"), year, day);
+                let known = answers::load(year, day).iter().flatten().count();
+                println!(
+                    "{} day {:02}  {:<32}  input: {:<3}  answers: {}/2",
+                    year,
+                    day,
+                    solution.title(),
+                    if input.exists() { "yes" } else { "no" },
+                    known
+                );
+            }
+        }
+        Command::New { year, day, title } => {
+            let title = title.clone().unwrap_or(format!("Day {}", day));
+            match scaffold::create(std::path::Path::new("."), *year, *day, &title) {
+                Ok(touched) => {
+                    for path in touched {
+                        println!("{}", path.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Failed to create {} day {}: {}", year, day, e);
+                    std::process::exit(2);
+                }
+            }
+        }
+    }
 }
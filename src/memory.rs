@@ -0,0 +1,139 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    counting: bool,
+    allocations: usize,
+    bytes: usize,
+    live: isize,
+    peak: isize,
+}
+
+// Counters are per thread so that days running side by side under --jobs
+// don't show up in each other's numbers.
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            counting: false,
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        if !c.counting {
+            return;
+        }
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated;
+        }
+        c.live += allocated as isize - freed as isize;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        return ptr;
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        return ptr;
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        return new_ptr;
+    }
+}
+
+// Counts what `f` allocates on the current thread. Peak is the most memory
+// `f` held at once, not counting what was live before it started.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    COUNTERS.with(|counters| {
+        counters.set(Counters {
+            counting: true,
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    });
+    let result = f();
+    let c = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.counting = false;
+        counters.set(c);
+        c
+    });
+    return (
+        result,
+        Usage {
+            allocations: c.allocations,
+            bytes: c.bytes,
+            peak: c.peak.max(0) as usize,
+        },
+    );
+}
+
+// Needs the counting allocator installed.
+#[cfg(all(test, feature = "memory"))]
+mod memory_tests {
+    use super::*;
+
+    #[test]
+    fn measure_vec() {
+        let (len, usage) = measure(|| {
+            let a = vec![0u8; 1000];
+            let b = vec![0u8; 3000];
+            drop(b);
+            let c = vec![0u8; 500];
+            a.len() + c.len()
+        });
+        assert_eq!(len, 1500);
+        assert_eq!(usage.allocations, 3);
+        assert_eq!(usage.bytes, 4500);
+        assert_eq!(usage.peak, 4000);
+    }
+
+    #[test]
+    fn other_threads_not_counted() {
+        let (_, usage) = measure(|| {
+            std::thread::scope(|scope| scope.spawn(|| vec![0u8; 1 << 20]).join().unwrap().len())
+        });
+        assert!(usage.bytes < 1 << 20);
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+// Where parsing an input went wrong. Lines and columns count from 1, columns
+// in characters. `file` is filled in by the runner, which knows where the
+// input came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    pub text: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    // The offending line with a caret under the column, rustc style.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        return format!(
+            "error: expected {}, found {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}^\n",
+            self.expected,
+            self.found,
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.text,
+            gutter,
+            " ".repeat(self.column - 1)
+        );
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: expected {}, found {}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            self.expected,
+            self.found
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+pub fn lines(input: &str) -> Vec<Line<'_>> {
+    return input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line {
+            number: i + 1,
+            text,
+        })
+        .collect();
+}
+
+// For inputs that stop before the parser got what it needed.
+pub fn end_of_input(input: &str, expected: &str) -> ParseError {
+    let text = input.lines().last().unwrap_or("");
+    return ParseError {
+        file: None,
+        line: input.lines().count().max(1),
+        column: text.chars().count() + 1,
+        expected: expected.to_string(),
+        found: "end of input".to_string(),
+        text: text.to_string(),
+    };
+}
+
+impl<'a> Line<'a> {
+    // `token` should be a slice of this line so the error can point at it. An
+    // empty token at the end of the line reads as "end of line".
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= self.text.len())
+            .unwrap_or(self.text.len());
+        let found = if token.is_empty() && offset == self.text.len() {
+            "end of line".to_string()
+        } else {
+            format!("`{}`", token)
+        };
+        return ParseError {
+            file: None,
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            expected: expected.to_string(),
+            found,
+            text: self.text.to_string(),
+        };
+    }
+
+    pub fn end(&self) -> &'a str {
+        return &self.text[self.text.len()..];
+    }
+
+    pub fn number<T: FromStr>(&self, token: &str) -> ParseResult<T> {
+        return token
+            .parse::<T>()
+            .map_err(|_| self.error(token, "a number"));
+    }
+
+    // The next token from `tokens`, or an error at the end of the line.
+    pub fn next(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        expected: &str,
+    ) -> ParseResult<&'a str> {
+        return tokens
+            .next()
+            .ok_or_else(|| self.error(self.end(), expected));
+    }
+
+    pub fn split_once(&self, within: &'a str, separator: &str) -> ParseResult<(&'a str, &'a str)> {
+        return within
+            .split_once(separator)
+            .ok_or_else(|| self.error(&within[within.len()..], &format!("`{}`", separator)));
+    }
+
+    pub fn strip_prefix(&self, within: &'a str, prefix: &str) -> ParseResult<&'a str> {
+        return within
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(within, &format!("`{}`", prefix)));
+    }
+
+    pub fn numbers<T: FromStr>(&self, within: &'a str, separator: char) -> ParseResult<Vec<T>> {
+        return within
+            .split(separator)
+            .filter(|token| separator != ' ' || !token.is_empty())
+            .map(|token| self.number(token.trim()))
+            .collect();
+    }
+}
+
+// One grid row of exactly `width` cells, one character each. `cell` turns a
+// character into a cell or rejects it.
+pub fn row<T>(
+    line: &Line,
+    width: usize,
+    expected: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> ParseResult<Vec<T>> {
+    let mut cells = Vec::with_capacity(width);
+    for (i, c) in line.text.char_indices() {
+        if cells.len() == width {
+            return Err(line.error(&line.text[i..], "end of line"));
+        }
+        match cell(c) {
+            Some(value) => cells.push(value),
+            None => return Err(line.error(&line.text[i..i + c.len_utf8()], expected)),
+        }
+    }
+    if cells.len() < width {
+        return Err(line.error(line.end(), expected));
+    }
+    return Ok(cells);
+}
+
+// A rectangular grid as wide as its first line.
+pub fn grid<T: Default + Clone>(
+    input: &str,
+    expected: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> ParseResult<Grid<T>> {
+    let lines = lines(input);
+    let Some(first) = lines.first() else {
+        return Err(end_of_input(input, expected));
+    };
+    let width = first.text.chars().count();
+    let mut grid = Grid::new(width, lines.len());
+    for (y, line) in lines.iter().enumerate() {
+        for (x, value) in row(line, width, expected, &cell)?.into_iter().enumerate() {
+            grid.set(x, y, value);
+        }
+    }
+    return Ok(grid);
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        let lines = lines("Time:      7  15   30\nDistance:  9  x  200");
+        let line = lines[1];
+        let token = line.text.split_whitespace().nth(2).unwrap();
+        let error = line.number::<i64>(token).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+        assert_eq!(error.found, "`x`");
+
+        let mut tokens = line.text.split_whitespace().skip(4);
+        let error = line.next(&mut tokens, "a distance").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (21, "end of line"));
+
+        let error = line.split_once(line.text, " -> ").unwrap_err();
+        assert_eq!(error.expected, "` -> `");
+        assert_eq!(line.numbers::<u32>("1,2,3", ',').unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn snippet() {
+        let lines = lines("px{a<2006:qkq,m>2090:A,rfg}\n{x=787,m=2655,q=1222,s=2876}");
+        let line = lines[1];
+        let mut error = line.error(&line.text[14..15], "one of x, m, a, s");
+        error.file = Some("input/2023/day19/input.txt".to_string());
+        assert_eq!(
+            error.to_string(),
+            "input/2023/day19/input.txt:2:15: expected one of x, m, a, s, found `q`"
+        );
+        assert_eq!(
+            error.snippet(),
+            "error: expected one of x, m, a, s, found `q`\n \
+             --> input/2023/day19/input.txt:2:15\n  \
+             |\n\
+             2 | {x=787,m=2655,q=1222,s=2876}\n  \
+             |               ^\n"
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let digit = |c: char| c.to_digit(10);
+        let parsed = grid("123\n456\n", "a digit", digit).unwrap();
+        assert_eq!(parsed.at(2, 1), Some(&6));
+
+        let error = grid("123\n4x6\n", "a digit", digit).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = grid("123\n45\n", "a digit", digit).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 3, "end of line")
+        );
+        let error = grid("123\n4567\n", "a digit", digit).unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (4, "`7`"));
+        assert_eq!(
+            grid("", "a digit", digit).unwrap_err().found,
+            "end of input"
+        );
+    }
+}
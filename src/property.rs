@@ -0,0 +1,220 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::solution::{Answer, Solution};
+use crate::utils::panic_message;
+
+// Cases per reference unless PROPERTY_CASES says otherwise. PROPERTY_SEED
+// replays a reported failure.
+const CASES: usize = 2000;
+const SEED: u64 = 0x2023_1225;
+const MAX_SHRINKS: usize = 1000;
+
+// SplitMix64. Small, and good enough to make up puzzle inputs.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        return Rng(seed);
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return z ^ (z >> 31);
+    }
+
+    // Uniform in `low..=high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        return low + (self.next_u64() % (high - low + 1) as u64) as i64;
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        return (self.next_u64() % n as u64) as usize;
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        return self.next_u64() % 100 < percent;
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        return items[self.below(items.len())];
+    }
+}
+
+// A slow but obviously right solver for one day, with a generator of small
+// cases in a form that is easy to shrink and a way to write them out as
+// puzzle input.
+pub struct Reference<T> {
+    pub solution: &'static dyn Solution,
+    pub parts: &'static [u32],
+    pub generate: fn(&mut Rng) -> T,
+    // Smaller variants of a case. Each must still be a valid input.
+    pub shrink: fn(&T) -> Vec<T>,
+    pub render: fn(&T) -> String,
+    pub solve: fn(&T, u32) -> Answer,
+}
+
+// Every way of leaving out one item, after leaving out either half.
+pub fn removals<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    if items.len() > 3 {
+        smaller.push(items[items.len() / 2..].to_vec());
+        smaller.push(items[..items.len() / 2].to_vec());
+    }
+    for i in 0..items.len() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        smaller.push(rest);
+    }
+    return smaller;
+}
+
+// How the solver and the reference disagree on `case`, if they do.
+fn mismatch<T>(reference: &Reference<T>, case: &T) -> Option<String> {
+    let input = (reference.render)(case);
+    let solution = reference.solution;
+    let describe = |result: std::thread::Result<Result<Answer, String>>| {
+        return match result {
+            Ok(Ok(answer)) => answer.to_string(),
+            Ok(Err(error)) => format!("invalid input ({})", error),
+            Err(payload) => format!("a panic ({})", panic_message(payload.as_ref())),
+        };
+    };
+    for &part in reference.parts {
+        let fast = catch_unwind(AssertUnwindSafe(|| {
+            let parsed = solution.parse(&input).map_err(|e| e.to_string())?;
+            return Ok(match part {
+                1 => solution.part1(parsed.as_ref()),
+                _ => solution.part2(parsed.as_ref()),
+            });
+        }));
+        let slow = catch_unwind(AssertUnwindSafe(|| Ok((reference.solve)(case, part))));
+        let (fast, slow) = (describe(fast), describe(slow));
+        if fast != slow {
+            return Some(format!(
+                "part {}: solver gave {}, reference {}",
+                part, fast, slow
+            ));
+        }
+    }
+    return None;
+}
+
+fn setting(name: &str, default: u64) -> u64 {
+    return std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default);
+}
+
+// Compares the solver with the reference on generated cases. The first case
+// they disagree on is shrunk as far as it goes and reported with the seed
+// that produced it.
+pub fn check<T>(reference: &Reference<T>) {
+    let seed = setting("PROPERTY_SEED", SEED);
+    let cases = setting("PROPERTY_CASES", CASES as u64);
+    let mut rng = Rng::new(seed);
+    for i in 0..cases {
+        let case = (reference.generate)(&mut rng);
+        let Some(mut reason) = mismatch(reference, &case) else {
+            continue;
+        };
+        let mut smallest = case;
+        let mut shrinks = 0;
+        'shrinking: while shrinks < MAX_SHRINKS {
+            for candidate in (reference.shrink)(&smallest) {
+                if let Some(other) = mismatch(reference, &candidate) {
+                    (smallest, reason) = (candidate, other);
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        panic!(
+            "{} day {} case {} of PROPERTY_SEED={} shrunk {} times to\n{}\n{}",
+            reference.solution.year(),
+            reference.solution.day(),
+            i,
+            seed,
+            shrinks,
+            (reference.render)(&smallest).trim_end(),
+            reason
+        );
+    }
+}
+
+#[cfg(test)]
+mod property_tests {
+    use super::*;
+
+    #[test]
+    fn rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let first = (0..100).map(|_| a.range(-3, 3)).collect::<Vec<_>>();
+        assert_eq!(first, (0..100).map(|_| b.range(-3, 3)).collect::<Vec<_>>());
+        assert!(first.iter().all(|n| (-3..=3).contains(n)));
+        assert!((-3..=3).all(|n| first.contains(&n)));
+    }
+
+    #[test]
+    fn shrinking() {
+        assert_eq!(
+            removals(&[1, 2, 3]),
+            vec![vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert_eq!(removals(&[1, 2, 3, 4, 5])[..2], [vec![3, 4, 5], vec![1, 2]]);
+    }
+
+    // Counts the lines of its input.
+    struct Lines;
+
+    impl Solution for Lines {
+        fn year(&self) -> u32 {
+            return 2023;
+        }
+
+        fn day(&self) -> u32 {
+            return 99;
+        }
+
+        fn title(&self) -> &'static str {
+            return "Lines";
+        }
+
+        fn parse(&self, input: &str) -> Result<Box<dyn std::any::Any>, crate::parse::ParseError> {
+            return Ok(Box::new(input.lines().count()));
+        }
+
+        fn part1(&self, input: &dyn std::any::Any) -> Answer {
+            return Answer::from(*input.downcast_ref::<usize>().unwrap());
+        }
+
+        fn part2(&self, _input: &dyn std::any::Any) -> Answer {
+            return Answer::Unsolved;
+        }
+    }
+
+    #[test]
+    fn reports_smallest_case() {
+        // The reference forgets numbers over 5, so the smallest disagreement
+        // is a single line with one of them.
+        let reference = Reference {
+            solution: &Lines,
+            parts: &[1],
+            generate: |rng| (0..rng.range(1, 8)).map(|_| rng.range(0, 9)).collect(),
+            shrink: |case: &Vec<i64>| removals(case),
+            render: |case| case.iter().map(|n| format!("{}\n", n)).collect(),
+            solve: |case, _| Answer::from(case.iter().filter(|n| **n <= 5).count()),
+        };
+        let payload = catch_unwind(AssertUnwindSafe(|| check(&reference))).unwrap_err();
+        let message = panic_message(payload.as_ref());
+        let lines = message.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("2023 day 99 case 0 of PROPERTY_SEED="));
+        assert!(lines[1].parse::<i64>().unwrap() > 5);
+        assert_eq!(lines[2], "part 1: solver gave 1, reference 0");
+    }
+}
@@ -0,0 +1,607 @@
+use std::io::{self, Write};
+
+use crate::answers::Status;
+use crate::baseline::{Comparison, Verdict};
+use crate::memory::Usage;
+use crate::solution::Answer;
+use crate::utils::{format_bytes, format_duration, SpanTotal, Stats};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub status: Status,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub comparison: Option<Comparison>,
+    pub memory: Option<Usage>,
+    pub spans: Vec<SpanTotal>,
+}
+
+// A record with no verdict and one-sample timings, for tests to adjust with
+// struct update syntax.
+#[cfg(test)]
+impl Record {
+    pub fn new(year: u32, day: u32, part: u32, answer: Answer) -> Record {
+        return Record {
+            year,
+            day,
+            part,
+            answer,
+            status: Status::Unknown,
+            parse: Stats::from_samples(&[1.0]),
+            solve: Stats::from_samples(&[1.0]),
+            comparison: None,
+            memory: None,
+            spans: Vec::new(),
+        };
+    }
+}
+
+// Optional columns, switched on by the command line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Columns {
+    pub baseline: bool,
+    pub memory: bool,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub fn reporter(&self, columns: Columns) -> Box<dyn Reporter> {
+        return match self {
+            Format::Table => Box::new(Table { columns }),
+            Format::Json => Box::new(Json { first: true }),
+            Format::Csv => Box::new(Csv { columns }),
+            Format::Markdown => Box::new(Markdown),
+        };
+    }
+}
+
+// Records are handed over one at a time so that slow days show up as soon as
+// they finish instead of after the whole run.
+pub trait Reporter {
+    fn header(&mut self, _out: &mut dyn Write) -> io::Result<()> {
+        return Ok(());
+    }
+    fn record(&mut self, out: &mut dyn Write, record: &Record) -> io::Result<()>;
+    fn footer(&mut self, _out: &mut dyn Write) -> io::Result<()> {
+        return Ok(());
+    }
+}
+
+pub fn render(reporter: &mut dyn Reporter, records: &[Record]) -> String {
+    let mut out = Vec::new();
+    reporter.header(&mut out).unwrap();
+    for record in records {
+        reporter.record(&mut out, record).unwrap();
+    }
+    reporter.footer(&mut out).unwrap();
+    return String::from_utf8(out).unwrap();
+}
+
+pub struct Table {
+    columns: Columns,
+}
+
+impl Table {
+    fn border(&self, left: &str, mid: &str, right: &str) -> String {
+        let mut widths = vec![4, 8, 32, 8, 12, 12, 12, 12, 12];
+        if self.columns.memory {
+            widths.extend([12, 12, 12]);
+        }
+        if self.columns.baseline {
+            widths.push(18);
+        }
+        let cells = widths.iter().map(|w| "─".repeat(*w)).collect::<Vec<_>>();
+        return format!("{}{}{}", left, cells.join(mid), right);
+    }
+}
+
+// Cuts long answers down to the column width so the borders stay aligned.
+fn fit(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        return s.to_string();
+    }
+    let mut fitted = s.chars().take(width - 1).collect::<String>();
+    fitted.push('…');
+    return fitted;
+}
+
+impl Reporter for Table {
+    fn header(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "{}", self.border("┌", "┬", "┐"))?;
+        write!(
+            out,
+            "│{:^4}│{:^8}│{:^32}│{:^8}│{:^12}│{:^12}│{:^12}│{:^12}│{:^12}│",
+            "Day", "Part", "Result", "Check", "Parse", "Solve", "Min", "P95", "Std Dev"
+        )?;
+        if self.columns.memory {
+            write!(out, "{:^12}│{:^12}│{:^12}│", "Allocs", "Allocated", "Peak")?;
+        }
+        if self.columns.baseline {
+            write!(out, "{:^18}│", "Parse / Solve Δ")?;
+        }
+        writeln!(out)
+    }
+
+    fn record(&mut self, out: &mut dyn Write, record: &Record) -> io::Result<()> {
+        // Parts that panicked or timed out have no timings to show.
+        let cell = |stats: &Stats, ns: f64| {
+            if stats.runs == 0 {
+                return String::new();
+            }
+            return format_duration(ns);
+        };
+        let result = match &record.status {
+            Status::Panicked(message) | Status::NoInput(message) => message.clone(),
+            Status::Invalid(error) => error.to_string(),
+            _ => record.answer.to_string(),
+        };
+        writeln!(out, "{}", self.border("├", "┼", "┤"))?;
+        write!(
+            out,
+            "│{:^4}│{:^8}│{:^32}│{:^8}│{:^12}│{:^12}│{:^12}│{:^12}│{:^12}│",
+            record.day,
+            record.part,
+            fit(&result, 32),
+            record.status.name(),
+            cell(&record.parse, record.parse.median),
+            cell(&record.solve, record.solve.median),
+            cell(&record.solve, record.solve.min),
+            cell(&record.solve, record.solve.p95),
+            cell(&record.solve, record.solve.stddev),
+        )?;
+        if self.columns.memory {
+            let (allocations, bytes, peak) = match &record.memory {
+                Some(usage) => (
+                    usage.allocations.to_string(),
+                    format_bytes(usage.bytes),
+                    format_bytes(usage.peak),
+                ),
+                None => (String::new(), String::new(), String::new()),
+            };
+            write!(out, "{:^12}│{:^12}│{:^12}│", allocations, bytes, peak)?;
+        }
+        if self.columns.baseline {
+            let comparison = match &record.comparison {
+                Some(comparison) => comparison.to_string(),
+                None => String::new(),
+            };
+            write!(out, "{:^18}│", comparison)?;
+        }
+        writeln!(out)
+    }
+
+    fn footer(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.border("└", "┴", "┘"))
+    }
+}
+
+pub struct Json {
+    first: bool,
+}
+
+fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    return escaped;
+}
+
+// Big integers are written as strings so that parsers using doubles keep
+// every digit.
+fn json_answer(answer: &Answer) -> String {
+    return match answer {
+        Answer::Integer(value) => value.to_string(),
+        Answer::BigInteger(value) => format!("\"{}\"", value),
+        Answer::String(value) => format!("\"{}\"", json_escape(value)),
+        Answer::Unsolved => "null".to_string(),
+    };
+}
+
+fn json_stats(stats: &Stats) -> String {
+    return format!(
+        "{{\"runs\": {}, \"mean_ns\": {}, \"min_ns\": {}, \"median_ns\": {}, \"p95_ns\": {}, \"stddev_ns\": {}}}",
+        stats.runs, stats.mean, stats.min, stats.median, stats.p95, stats.stddev
+    );
+}
+
+fn status_name(status: &Status) -> &'static str {
+    return match status {
+        Status::Pass => "pass",
+        Status::Fail(_) => "fail",
+        Status::Unknown => "unknown",
+        Status::Panicked(_) => "failed",
+        Status::TimedOut => "timeout",
+        Status::Invalid(_) => "invalid",
+        Status::NoInput(_) => "no_input",
+    };
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+    return match verdict {
+        Verdict::Regressed => "regressed",
+        Verdict::Improved => "improved",
+        Verdict::Unchanged => "unchanged",
+        Verdict::New => "new",
+    };
+}
+
+impl Reporter for Json {
+    fn header(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        self.first = true;
+        write!(out, "[")
+    }
+
+    fn record(&mut self, out: &mut dyn Write, record: &Record) -> io::Result<()> {
+        if !self.first {
+            write!(out, ",")?;
+        }
+        self.first = false;
+        write!(
+            out,
+            "\n  {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"status\": \"{}\", \"parse\": {}, \"solve\": {}",
+            record.year,
+            record.day,
+            record.part,
+            json_answer(&record.answer),
+            status_name(&record.status),
+            json_stats(&record.parse),
+            json_stats(&record.solve),
+        )?;
+        match &record.status {
+            Status::Fail(expected) => write!(out, ", \"expected\": \"{}\"", json_escape(expected))?,
+            Status::Panicked(message) | Status::NoInput(message) => {
+                write!(out, ", \"error\": \"{}\"", json_escape(message))?
+            }
+            Status::Invalid(error) => {
+                write!(out, ", \"error\": \"{}\"", json_escape(&error.to_string()))?
+            }
+            _ => {}
+        }
+        if let Some(usage) = &record.memory {
+            write!(
+                out,
+                ", \"memory\": {{\"allocations\": {}, \"bytes\": {}, \"peak_bytes\": {}}}",
+                usage.allocations, usage.bytes, usage.peak
+            )?;
+        }
+        if let Some(comparison) = &record.comparison {
+            write!(
+                out,
+                ", \"baseline\": {{\"parse_change\": {}, \"solve_change\": {}, \"verdict\": \"{}\"}}",
+                comparison.parse,
+                comparison.solve,
+                verdict_name(comparison.verdict)
+            )?;
+        }
+        write!(out, "}}")
+    }
+
+    fn footer(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "\n]")
+    }
+}
+
+pub struct Csv {
+    columns: Columns,
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", s.replace('"', "\"\""));
+    }
+    return s.to_string();
+}
+
+fn csv_stats(stats: &Stats) -> String {
+    return format!(
+        "{},{},{},{},{},{}",
+        stats.runs, stats.mean, stats.min, stats.median, stats.p95, stats.stddev
+    );
+}
+
+impl Reporter for Csv {
+    fn header(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        write!(
+            out,
+            "year,day,part,answer,status,expected,error,\
+             parse_runs,parse_mean_ns,parse_min_ns,parse_median_ns,parse_p95_ns,parse_stddev_ns,\
+             solve_runs,solve_mean_ns,solve_min_ns,solve_median_ns,solve_p95_ns,solve_stddev_ns"
+        )?;
+        if self.columns.memory {
+            write!(out, ",allocations,bytes,peak_bytes")?;
+        }
+        if self.columns.baseline {
+            write!(out, ",parse_change,solve_change,verdict")?;
+        }
+        writeln!(out)
+    }
+
+    fn record(&mut self, out: &mut dyn Write, record: &Record) -> io::Result<()> {
+        write!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            record.year,
+            record.day,
+            record.part,
+            match &record.answer {
+                Answer::Unsolved => String::new(),
+                answer => csv_escape(&answer.to_string()),
+            },
+            status_name(&record.status),
+            match &record.status {
+                Status::Fail(expected) => csv_escape(expected),
+                _ => String::new(),
+            },
+            match &record.status {
+                Status::Panicked(message) | Status::NoInput(message) => csv_escape(message),
+                Status::Invalid(error) => csv_escape(&error.to_string()),
+                _ => String::new(),
+            },
+            csv_stats(&record.parse),
+            csv_stats(&record.solve),
+        )?;
+        if self.columns.memory {
+            match &record.memory {
+                Some(usage) => {
+                    write!(out, ",{},{},{}", usage.allocations, usage.bytes, usage.peak)?
+                }
+                None => write!(out, ",,,")?,
+            }
+        }
+        if self.columns.baseline {
+            match &record.comparison {
+                Some(comparison) => write!(
+                    out,
+                    ",{},{},{}",
+                    comparison.parse,
+                    comparison.solve,
+                    verdict_name(comparison.verdict)
+                )?,
+                None => write!(out, ",,,")?,
+            }
+        }
+        writeln!(out)
+    }
+}
+
+// Same layout as the "Runtimes" table in README.md: answers left out, mean
+// solve time in whole microseconds. Parts without an answer have no time
+// worth showing and get no row.
+pub struct Markdown;
+
+impl Reporter for Markdown {
+    fn header(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "``` text")?;
+        writeln!(out)?;
+        writeln!(out, "┌────┬────────┬────────────┐")?;
+        write!(out, "│{:^4}│{:^8}│{:^12}│", "Day", "Part", "Time (us)")
+    }
+
+    fn record(&mut self, out: &mut dyn Write, record: &Record) -> io::Result<()> {
+        if record.status.is_error() || matches!(record.answer, Answer::Unsolved) {
+            return Ok(());
+        }
+        writeln!(out)?;
+        writeln!(out, "├────┼────────┼────────────┤")?;
+        write!(
+            out,
+            "│{:^4}│{:^8}│{:^12}│",
+            record.day,
+            record.part,
+            format!("{:.0}", record.solve.mean / 1e3)
+        )
+    }
+
+    fn footer(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "└────┴────────┴────────────┘")?;
+        writeln!(out)?;
+        writeln!(out, "```")
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    fn record(day: u32, part: u32, answer: Answer, time_us: f64) -> Record {
+        return Record {
+            parse: Stats::from_samples(&[500.0]),
+            solve: Stats::from_samples(&[time_us * 1e3]),
+            ..Record::new(2023, day, part, answer)
+        };
+    }
+
+    fn records() -> Vec<Record> {
+        return vec![
+            record(1, 1, Answer::Integer(142), 28.0),
+            Record {
+                status: Status::Fail("c".to_string()),
+                ..record(5, 2, Answer::from("a,\"b\""), 154889348.0)
+            },
+        ];
+    }
+
+    #[test]
+    fn markdown() {
+        let expected = "``` text
+
+┌────┬────────┬────────────┐
+│Day │  Part  │ Time (us)  │
+├────┼────────┼────────────┤
+│ 1  │   1    │     28     │
+├────┼────────┼────────────┤
+│ 5  │   2    │ 154889348  │
+└────┴────────┴────────────┘
+
+```
+";
+        assert_eq!(render(&mut Markdown, &records()), expected);
+
+        // Like 25/2, which has no puzzle, or a part that panicked.
+        let mut more = records();
+        more.push(record(25, 2, Answer::Unsolved, 0.0));
+        more.push(Record {
+            status: Status::Panicked("boom".to_string()),
+            ..record(14, 2, Answer::Unsolved, 0.0)
+        });
+        more.push(Record {
+            status: Status::TimedOut,
+            ..record(17, 1, Answer::Integer(102), 0.0)
+        });
+        assert_eq!(render(&mut Markdown, &more), expected);
+    }
+
+    #[test]
+    fn json() {
+        let expected = r#"[
+  {"year": 2023, "day": 1, "part": 1, "answer": 142, "status": "unknown", "parse": {"runs": 1, "mean_ns": 500, "min_ns": 500, "median_ns": 500, "p95_ns": 500, "stddev_ns": 0}, "solve": {"runs": 1, "mean_ns": 28000, "min_ns": 28000, "median_ns": 28000, "p95_ns": 28000, "stddev_ns": 0}},
+  {"year": 2023, "day": 5, "part": 2, "answer": "a,\"b\"", "status": "fail", "parse": {"runs": 1, "mean_ns": 500, "min_ns": 500, "median_ns": 500, "p95_ns": 500, "stddev_ns": 0}, "solve": {"runs": 1, "mean_ns": 154889348000, "min_ns": 154889348000, "median_ns": 154889348000, "p95_ns": 154889348000, "stddev_ns": 0}, "expected": "c"}
+]
+"#;
+        assert_eq!(
+            render(&mut *Format::Json.reporter(Columns::default()), &records()),
+            expected
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let expected = "year,day,part,answer,status,expected,error,\
+             parse_runs,parse_mean_ns,parse_min_ns,parse_median_ns,parse_p95_ns,parse_stddev_ns,\
+             solve_runs,solve_mean_ns,solve_min_ns,solve_median_ns,solve_p95_ns,solve_stddev_ns
+2023,1,1,142,unknown,,,1,500,500,500,500,0,1,28000,28000,28000,28000,0
+2023,5,2,\"a,\"\"b\"\"\",fail,c,,1,500,500,500,500,0,1,154889348000,154889348000,154889348000,154889348000,0
+";
+        assert_eq!(
+            render(&mut *Format::Csv.reporter(Columns::default()), &records()),
+            expected
+        );
+    }
+
+    #[test]
+    fn table() {
+        let mut table = Format::Table.reporter(Columns::default());
+        let rendered = render(&mut *table, &records()[..1]);
+        let row = rendered.lines().nth(4).unwrap();
+        assert_eq!(
+            row,
+            "│ 1  │   1    │              142               │   ?    │   500 ns   │  28.00 µs  │  28.00 µs  │  28.00 µs  │    0 ns    │"
+        );
+    }
+
+    #[test]
+    fn table_answers() {
+        let records = vec![
+            record(25, 2, Answer::Unsolved, 1.0),
+            record(24, 2, Answer::BigInteger(i128::MAX), 1.0),
+        ];
+        let mut table = Format::Table.reporter(Columns::default());
+        let rendered = render(&mut *table, &records);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[4].starts_with("│ 25 │   2    │               —                │"));
+        assert!(lines[6].starts_with("│ 24 │   2    │1701411834604692317316873037158…│"));
+        assert_eq!(lines[4].chars().count(), lines[6].chars().count());
+
+        let json = render(&mut *Format::Json.reporter(Columns::default()), &records);
+        assert!(json.contains("\"answer\": null"));
+        assert!(json.contains(&format!("\"answer\": \"{}\"", i128::MAX)));
+    }
+
+    #[test]
+    fn table_errors() {
+        let records = vec![
+            Record {
+                status: Status::Panicked("called `Option::unwrap()` on a `None` value".to_string()),
+                parse: Stats::default(),
+                solve: Stats::default(),
+                ..record(17, 1, Answer::Unsolved, 0.0)
+            },
+            Record {
+                status: Status::TimedOut,
+                solve: Stats::default(),
+                ..record(5, 2, Answer::Unsolved, 0.0)
+            },
+        ];
+        let mut table = Format::Table.reporter(Columns::default());
+        let rendered = render(&mut *table, &records);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[4],
+            "│ 17 │   1    │called `Option::unwrap()` on a …│ FAILED │            │            │            │            │            │"
+        );
+        assert_eq!(
+            lines[6],
+            "│ 5  │   2    │               —                │TIMEOUT │   500 ns   │            │            │            │            │"
+        );
+    }
+
+    #[test]
+    fn memory() {
+        let mut records = records();
+        records[0].memory = Some(Usage {
+            allocations: 12,
+            bytes: 3 << 20,
+            peak: 1536,
+        });
+        let columns = Columns {
+            baseline: false,
+            memory: true,
+        };
+        let rendered = render(&mut *Format::Table.reporter(columns), &records);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[2].ends_with("│   Allocs   │ Allocated  │    Peak    │"));
+        assert!(lines[4].ends_with("│     12     │  3.00 MiB  │  1.50 KiB  │"));
+        assert!(lines[6].ends_with("│            │            │            │"));
+
+        let csv = render(&mut *Format::Csv.reporter(columns), &records);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert!(lines[0].ends_with(",allocations,bytes,peak_bytes"));
+        assert!(lines[1].ends_with(",12,3145728,1536"));
+        assert!(lines[2].ends_with(",,,"));
+    }
+
+    #[test]
+    fn table_baseline() {
+        let mut records = records();
+        records[0].comparison = Some(Comparison {
+            parse: 0.0,
+            solve: 0.25,
+            verdict: Verdict::Regressed,
+        });
+        let mut table = Format::Table.reporter(Columns {
+            baseline: true,
+            memory: false,
+        });
+        let rendered = render(&mut *table, &records);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[2].ends_with("│ Parse / Solve Δ  │"));
+        assert!(lines[4].ends_with("│   +0% / +25% ▲   │"));
+        assert!(lines[6].ends_with("│                  │"));
+        assert!(lines
+            .iter()
+            .skip(1)
+            .all(|line| line.chars().count() == lines[1].chars().count()));
+    }
+}
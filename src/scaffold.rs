@@ -0,0 +1,164 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub fn module_source(year: u32, day: u32, title: &str) -> String {
+    let template = r#"use crate::parse::ParseResult;
+use crate::solution::Answer;
+use crate::utils::main;
+
+fn get_contents(input: &str) -> ParseResult<Vec<String>> {
+    return Ok(crate::utils::lines(input));
+}
+
+fn part1(_input: &Vec<String>) -> Answer {
+    return Answer::Unsolved;
+}
+
+fn part2(_input: &Vec<String>) -> Answer {
+    return Answer::Unsolved;
+}
+
+// Examples go in test_inputs/YYYY/dayNN/testNN.txt, with the expected answers in
+// testNN.part1 and testNN.part2 next to them.
+main!("TITLE");
+"#;
+    return template
+        .replace("YYYY", &year.to_string())
+        .replace("dayNN", &format!("day{:02}", day))
+        .replace("TITLE", &title.replace('\\', "\\\\").replace('"', "\\\""));
+}
+
+// Adds `module` to the `days!(...)` list, keeping it sorted. Returns None when
+// it is already there.
+pub fn register_day(source: &str, module: &str) -> Option<String> {
+    let start = source.find("days!(")? + "days!(".len();
+    let end = start + source[start..].find(')')?;
+    let mut modules = source[start..end]
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    if modules.contains(&module) {
+        return None;
+    }
+    modules.push(module);
+    modules.sort();
+    // Wrapped the way rustfmt lays out the list.
+    let mut list = String::new();
+    let mut line = String::from("   ");
+    for module in modules {
+        if line.len() + module.len() + 2 > 100 {
+            list.push_str(&line);
+            list.push('\n');
+            line = String::from("   ");
+        }
+        line.push_str(&format!(" {},", module));
+    }
+    list.push_str(&line);
+    return Some(format!(
+        "{}\n{}\n{}",
+        &source[..start],
+        list,
+        &source[end..]
+    ));
+}
+
+// Adds `pub mod yearYYYY;` and its solutions to main.rs.
+pub fn register_year(source: &str, year: u32) -> Option<String> {
+    let module = format!("year{}", year);
+    if source.contains(&format!("pub mod {};", module)) {
+        return None;
+    }
+    let last_mod = source.rfind("pub mod ")?;
+    let after_mod = last_mod + source[last_mod..].find('\n')? + 1;
+    let list_end = source.find("].concat()")?;
+    return Some(format!(
+        "{}pub mod {};\n{}, {}::solutions(){}",
+        &source[..after_mod],
+        module,
+        source[after_mod..list_end].trim_end_matches([',', ' ']),
+        module,
+        &source[list_end..]
+    ));
+}
+
+// Writes the skeleton for `day`, an empty test_inputs directory for it and
+// registers the module. Paths are relative to the repository root. Returns
+// every file or directory that was created or changed.
+pub fn create(root: &Path, year: u32, day: u32, title: &str) -> io::Result<Vec<PathBuf>> {
+    let module = format!("day{:02}", day);
+    let year_file = root.join(format!("src/year{}.rs", year));
+    let day_file = root.join(format!("src/year{}/{}.rs", year, module));
+    if day_file.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", day_file.display()),
+        ));
+    }
+    let mut touched = Vec::new();
+
+    if !year_file.exists() {
+        let main_file = root.join("src/main.rs");
+        let main_source = std::fs::read_to_string(&main_file)?;
+        let Some(main_source) = register_year(&main_source, year) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("could not register year{} in src/main.rs", year),
+            ));
+        };
+        std::fs::write(&main_file, main_source)?;
+        std::fs::write(&year_file, "use crate::utils::days;\n\ndays!();\n")?;
+        touched.push(main_file);
+    }
+    let year_source = std::fs::read_to_string(&year_file)?;
+    if let Some(year_source) = register_day(&year_source, &module) {
+        std::fs::write(&year_file, year_source)?;
+        touched.push(year_file);
+    }
+
+    std::fs::create_dir_all(day_file.parent().unwrap())?;
+    std::fs::write(&day_file, module_source(year, day, title))?;
+    touched.push(day_file);
+
+    let test_dir = root.join(crate::examples::dir(year, day));
+    std::fs::create_dir_all(&test_dir)?;
+    touched.push(test_dir);
+    return Ok(touched);
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+    use super::*;
+
+    #[test]
+    fn module() {
+        let source = module_source(2023, 7, "Camel \"Cards\"");
+        assert!(source.contains("test_inputs/2023/day07/testNN.txt"));
+        assert!(source.ends_with("testNN.part2 next to them.\nmain!(\"Camel \\\"Cards\\\"\");\n"));
+    }
+
+    #[test]
+    fn days_list() {
+        let source = "use crate::utils::days;\n\ndays!(\n    day01, day02, day10,\n);\n";
+        assert_eq!(
+            register_day(source, "day03").unwrap(),
+            "use crate::utils::days;\n\ndays!(\n    day01, day02, day03, day10,\n);\n"
+        );
+        assert_eq!(register_day(source, "day02"), None);
+        let current = std::fs::read_to_string("src/year2023.rs").unwrap();
+        let rebuilt = register_day(&current.replace("day25,", ""), "day25").unwrap();
+        assert_eq!(rebuilt, current);
+        assert_eq!(
+            register_day("days!();\n", "day01").unwrap(),
+            "days!(\n    day01,\n);\n"
+        );
+    }
+
+    #[test]
+    fn years() {
+        let source = "pub mod utils;\npub mod year2023;\n\npub fn solutions() -> Vec<&'static dyn solution::Solution> {\n    return [year2023::solutions()].concat();\n}\n";
+        let updated = register_year(source, 2024).unwrap();
+        assert!(updated.contains("pub mod year2023;\npub mod year2024;\n"));
+        assert!(updated.contains("[year2023::solutions(), year2024::solutions()].concat()"));
+        assert_eq!(register_year(&updated, 2024), None);
+    }
+}
@@ -0,0 +1,74 @@
+// A sorted set of day or part numbers picked on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranges(pub Vec<u32>);
+
+impl Ranges {
+    pub fn contains(&self, n: u32) -> bool {
+        return self.0.binary_search(&n).is_ok();
+    }
+}
+
+// Parses selectors like "1-5,10,20-25". Every number has to be within
+// `min..=max`.
+pub fn parse_ranges(spec: &str, min: u32, max: u32) -> Result<Ranges, String> {
+    let mut numbers = Vec::new();
+    for item in spec.split(',').map(str::trim) {
+        let number = |s: &str| {
+            let n = s
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("`{}` is not a number", s.trim()))?;
+            if n < min || n > max {
+                return Err(format!("{} is not in {}-{}", n, min, max));
+            }
+            return Ok(n);
+        };
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (number(from)?, number(to)?);
+                if from > to {
+                    return Err(format!("`{}` is an empty range", item));
+                }
+                numbers.extend(from..=to);
+            }
+            None => numbers.push(number(item)?),
+        }
+    }
+    numbers.sort();
+    numbers.dedup();
+    return Ok(Ranges(numbers));
+}
+
+pub fn days(spec: &str) -> Result<Ranges, String> {
+    return parse_ranges(spec, 1, 25);
+}
+
+pub fn parts(spec: &str) -> Result<Ranges, String> {
+    return parse_ranges(spec, 1, 2);
+}
+
+#[cfg(test)]
+mod select_tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        assert_eq!(
+            days("1-5,10,20-25").unwrap().0,
+            vec![1, 2, 3, 4, 5, 10, 20, 21, 22, 23, 24, 25]
+        );
+        assert_eq!(days("7, 3,3-4").unwrap().0, vec![3, 4, 7]);
+        assert_eq!(parts("2").unwrap().0, vec![2]);
+        assert!(days("1-5").unwrap().contains(4));
+        assert!(!days("1-5").unwrap().contains(6));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(days("26").unwrap_err(), "26 is not in 1-25");
+        assert_eq!(days("0-3").unwrap_err(), "0 is not in 1-25");
+        assert_eq!(days("5-1").unwrap_err(), "`5-1` is an empty range");
+        assert_eq!(days("1,,2").unwrap_err(), "`` is not a number");
+        assert_eq!(parts("3").unwrap_err(), "3 is not in 1-2");
+    }
+}
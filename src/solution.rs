@@ -0,0 +1,311 @@
+use std::any::Any;
+use std::fmt::Display;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use crate::answers::Status;
+use crate::memory::measure;
+use crate::parse::ParseError;
+use crate::report::Record;
+use crate::utils::{panic_message, time_it, trace, Stats};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    String(String),
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigInteger(value) => write!(f, "{}", value),
+            Answer::String(value) => write!(f, "{}", value),
+            Answer::Unsolved => write!(f, "—"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    return match i64::try_from(value) {
+                        Ok(value) => Answer::Integer(value),
+                        Err(_) => Answer::BigInteger(value as i128),
+                    };
+                }
+            }
+        )+
+    };
+}
+
+answer_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        return Answer::String(value);
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        return Answer::String(value.to_string());
+    }
+}
+
+// Parsed inputs are type-erased so that every day can sit behind the same
+// `&dyn Solution` in the registry. The `main!` macro does the downcasting.
+pub trait Solution: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any) -> Answer;
+    fn part2(&self, input: &dyn Any) -> Answer;
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub parts: Vec<u32>,
+    pub num_runs: usize,
+    pub warmup: usize,
+    pub timeout: Option<Duration>,
+    pub memory: bool,
+    pub trace: bool,
+}
+
+fn failed_part(solution: &dyn Solution, part: u32, status: Status) -> Record {
+    return Record {
+        year: solution.year(),
+        day: solution.day(),
+        part,
+        answer: Answer::Unsolved,
+        status,
+        parse: Stats::default(),
+        solve: Stats::default(),
+        comparison: None,
+        memory: None,
+        spans: Vec::new(),
+    };
+}
+
+// Records for every selected part of a day that could not be run at all.
+pub fn failed(solution: &dyn Solution, options: &RunOptions, status: Status) -> Vec<Record> {
+    return options
+        .parts
+        .iter()
+        .map(|&part| failed_part(solution, part, status.clone()))
+        .collect();
+}
+
+fn run_parts(
+    solution: &dyn Solution,
+    options: &RunOptions,
+    input: &str,
+    mut emit: impl FnMut(Record),
+) {
+    let parsed = catch_unwind(AssertUnwindSafe(|| {
+        time_it(|| solution.parse(input), &options.num_runs, &options.warmup)
+    }));
+    let (contents, parse) = match parsed {
+        Ok((Ok(contents), parse)) => (contents, parse),
+        Ok((Err(error), _)) => {
+            failed(solution, options, Status::Invalid(error))
+                .into_iter()
+                .for_each(emit);
+            return;
+        }
+        Err(payload) => {
+            let status = Status::Panicked(panic_message(payload.as_ref()));
+            failed(solution, options, status).into_iter().for_each(emit);
+            return;
+        }
+    };
+    for &part in options.parts.iter() {
+        let solved = catch_unwind(AssertUnwindSafe(|| {
+            time_it(
+                || match part {
+                    1 => solution.part1(contents.as_ref()),
+                    _ => solution.part2(contents.as_ref()),
+                },
+                &options.num_runs,
+                &options.warmup,
+            )
+        }));
+        // Allocations are counted and spans traced on separate runs so that
+        // neither ends up in the timings.
+        let solve_once = || match part {
+            1 => solution.part1(contents.as_ref()),
+            _ => solution.part2(contents.as_ref()),
+        };
+        let memory = match (&solved, options.memory) {
+            (Ok(_), true) => catch_unwind(AssertUnwindSafe(|| measure(solve_once).1)).ok(),
+            _ => None,
+        };
+        let spans = match (&solved, options.trace) {
+            (Ok(_), true) => {
+                let root = if part == 1 { "part1" } else { "part2" };
+                catch_unwind(AssertUnwindSafe(|| trace(root, solve_once).1)).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+        let record = match solved {
+            Ok((answer, solve)) => Record {
+                year: solution.year(),
+                day: solution.day(),
+                part,
+                answer,
+                status: Status::Unknown,
+                parse,
+                solve,
+                comparison: None,
+                memory,
+                spans,
+            },
+            Err(payload) => {
+                let status = Status::Panicked(panic_message(payload.as_ref()));
+                Record {
+                    parse,
+                    ..failed_part(solution, part, status)
+                }
+            }
+        };
+        emit(record);
+    }
+}
+
+// With a timeout the day runs on its own thread. A thread that overruns cannot
+// be stopped, so it is left behind and dies with the process.
+pub fn run(solution: &'static dyn Solution, options: &RunOptions, input: &str) -> Vec<Record> {
+    let Some(timeout) = options.timeout else {
+        let mut records = Vec::new();
+        run_parts(solution, options, input, |record| records.push(record));
+        return records;
+    };
+    let (tx, rx) = mpsc::channel();
+    let thread_options = options.clone();
+    let thread_input = input.to_string();
+    std::thread::spawn(move || {
+        run_parts(solution, &thread_options, &thread_input, |record| {
+            let _ = tx.send(record);
+        })
+    });
+    let deadline = Instant::now() + timeout;
+    let mut records = Vec::new();
+    for &part in options.parts.iter() {
+        let status = match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(record) => {
+                records.push(record);
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => Status::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => {
+                Status::Panicked("solver thread exited".to_string())
+            }
+        };
+        records.push(failed_part(solution, part, status));
+    }
+    return records;
+}
+
+#[cfg(test)]
+mod answer_tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        assert_eq!(Answer::from(142i32), Answer::Integer(142));
+        assert_eq!(Answer::from(71503usize), Answer::Integer(71503));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as i128));
+        assert_eq!(Answer::from("abc"), Answer::String("abc".to_string()));
+        assert_eq!(Answer::Unsolved.to_string(), "—");
+    }
+}
+
+#[cfg(test)]
+mod run_tests {
+    use super::*;
+
+    struct Flaky;
+
+    impl Solution for Flaky {
+        fn year(&self) -> u32 {
+            return 2023;
+        }
+
+        fn day(&self) -> u32 {
+            return 99;
+        }
+
+        fn title(&self) -> &'static str {
+            return "Flaky";
+        }
+
+        fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+            if input.is_empty() {
+                panic!("no input");
+            }
+            if input == "?" {
+                return Err(crate::parse::end_of_input(input, "a number"));
+            }
+            return Ok(Box::new(input.len()));
+        }
+
+        fn part1(&self, _input: &dyn Any) -> Answer {
+            panic!("boom");
+        }
+
+        fn part2(&self, input: &dyn Any) -> Answer {
+            std::thread::sleep(Duration::from_millis(500));
+            return Answer::from(*input.downcast_ref::<usize>().unwrap());
+        }
+    }
+
+    fn options(timeout: Option<Duration>) -> RunOptions {
+        return RunOptions {
+            parts: vec![1, 2],
+            num_runs: 1,
+            warmup: 0,
+            timeout,
+            memory: false,
+            trace: false,
+        };
+    }
+
+    #[test]
+    fn panics() {
+        let records = run(&Flaky, &options(None), "abc");
+        assert_eq!(records[0].status, Status::Panicked("boom".to_string()));
+        assert_eq!(records[1].status, Status::Unknown);
+        assert_eq!(records[1].answer, Answer::Integer(3));
+
+        let records = run(&Flaky, &options(None), "");
+        assert_eq!(records.len(), 2);
+        assert!(records
+            .iter()
+            .all(|r| r.status == Status::Panicked("no input".to_string())));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let records = run(&Flaky, &options(None), "?");
+        assert_eq!(records.len(), 2);
+        assert!(records
+            .iter()
+            .all(|r| matches!(&r.status, Status::Invalid(e) if e.expected == "a number")));
+    }
+
+    #[test]
+    fn timeout() {
+        let records = run(&Flaky, &options(Some(Duration::from_millis(50))), "abc");
+        assert_eq!(records[0].status, Status::Panicked("boom".to_string()));
+        assert_eq!(records[1].part, 2);
+        assert_eq!(records[1].status, Status::TimedOut);
+    }
+}
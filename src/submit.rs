@@ -0,0 +1,325 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::examples;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, without a hint which way.
+    Wrong,
+    RateLimited,
+    AlreadySolved,
+    Unrecognised,
+}
+
+const OUTCOMES: [(Outcome, &str); 7] = [
+    (Outcome::Correct, "correct"),
+    (Outcome::TooHigh, "too_high"),
+    (Outcome::TooLow, "too_low"),
+    (Outcome::Wrong, "wrong"),
+    (Outcome::RateLimited, "rate_limited"),
+    (Outcome::AlreadySolved, "already_solved"),
+    (Outcome::Unrecognised, "unrecognised"),
+];
+
+impl Outcome {
+    pub fn name(&self) -> &'static str {
+        return OUTCOMES.iter().find(|(o, _)| o == self).unwrap().1;
+    }
+
+    fn from_name(name: &str) -> Option<Outcome> {
+        return OUTCOMES.iter().find(|(_, n)| *n == name).map(|(o, _)| *o);
+    }
+
+    fn is_wrong(&self) -> bool {
+        return matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong);
+    }
+}
+
+// What the site made of an answer, and how long it wants us to wait before
+// the next one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub outcome: Outcome,
+    pub wait: Option<Duration>,
+    pub message: String,
+}
+
+fn number(word: &str) -> Option<u64> {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    return match words.iter().position(|w| *w == word) {
+        Some(i) => Some(i as u64 + 1),
+        None => word.parse().ok(),
+    };
+}
+
+// Either "You have 1m 23s left to wait" or "please wait 5 minutes before
+// trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        let mut seconds = 0;
+        for token in rest[..end].split_whitespace() {
+            let (n, unit) = token.split_at(token.len() - 1);
+            let n = n.parse::<u64>().ok()?;
+            seconds += match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                "s" => n,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+    let rest = &text[text.find("wait ")? + "wait ".len()..];
+    let mut words = rest.split_whitespace();
+    let n = number(words.next()?)?;
+    let unit = words.next()?;
+    let scale = if unit.starts_with("second") {
+        1
+    } else if unit.starts_with("minute") {
+        60
+    } else if unit.starts_with("hour") {
+        3600
+    } else {
+        return None;
+    };
+    return Some(Duration::from_secs(n * scale));
+}
+
+pub fn parse_response(html: &str) -> Response {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let text = examples::unescape(&examples::strip_tags(article));
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    // Drop the "[Return to Day 6]" link.
+    let message = text.split(" [").next().unwrap_or("").trim().to_string();
+
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognised
+    };
+    let wait = match outcome {
+        Outcome::Correct | Outcome::AlreadySolved | Outcome::Unrecognised => None,
+        _ => parse_wait(&text),
+    };
+    return Response {
+        outcome,
+        wait,
+        message,
+    };
+}
+
+// One line of the submission log. `time` is in seconds since the epoch and
+// `wait` is the cooldown the site asked for, in seconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub time: u64,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub wait: u64,
+}
+
+pub fn log_path(year: u32, day: u32) -> PathBuf {
+    return PathBuf::from(format!("answers/{}/day{:02}.log", year, day));
+}
+
+// Tab separated: time, part, answer, outcome, wait. Lines that do not parse
+// are skipped.
+pub fn parse_log(contents: &str) -> Vec<Submission> {
+    return contents
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let [time, part, answer, outcome, wait] = fields[..] else {
+                return None;
+            };
+            return Some(Submission {
+                time: time.parse().ok()?,
+                part: part.parse().ok()?,
+                answer: answer.to_string(),
+                outcome: Outcome::from_name(outcome)?,
+                wait: wait.parse().ok()?,
+            });
+        })
+        .collect();
+}
+
+pub fn load(year: u32, day: u32) -> Vec<Submission> {
+    return match std::fs::read_to_string(log_path(year, day)) {
+        Ok(contents) => parse_log(&contents),
+        Err(_) => Vec::new(),
+    };
+}
+
+pub fn append(year: u32, day: u32, submission: &Submission) -> io::Result<()> {
+    let path = log_path(year, day);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    return writeln!(
+        file,
+        "{}\t{}\t{}\t{}\t{}",
+        submission.time,
+        submission.part,
+        submission.answer,
+        submission.outcome.name(),
+        submission.wait
+    );
+}
+
+// Why `answer` should not be sent for `part` at `now`, going by earlier
+// submissions.
+pub fn refusal(history: &[Submission], part: u32, answer: &str, now: u64) -> Option<String> {
+    let until = history.iter().map(|s| s.time + s.wait).max().unwrap_or(0);
+    if now < until {
+        return Some(format!("the site asked to wait another {}s", until - now));
+    }
+    let bound = |other: &str| Some((answer.parse::<i128>().ok()?, other.parse::<i128>().ok()?));
+    for submission in history.iter().filter(|s| s.part == part) {
+        let previous = &submission.answer;
+        match (submission.outcome, bound(previous)) {
+            (Outcome::Correct, _) => {
+                return Some(format!(
+                    "part {} was already solved with {}",
+                    part, previous
+                ))
+            }
+            (Outcome::AlreadySolved, _) => return Some(format!("part {} is already solved", part)),
+            (outcome, _) if outcome.is_wrong() && previous == answer => {
+                return Some(format!("{} was already rejected", answer))
+            }
+            (Outcome::TooHigh, Some((n, high))) if n >= high => {
+                return Some(format!(
+                    "{} is not below {}, which was too high",
+                    answer, previous
+                ))
+            }
+            (Outcome::TooLow, Some((n, low))) if n <= low => {
+                return Some(format!(
+                    "{} is not above {}, which was too low",
+                    answer, previous
+                ))
+            }
+            _ => {}
+        }
+    }
+    return None;
+}
+
+#[cfg(test)]
+mod submit_tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        return format!(
+            "<!DOCTYPE html>\n<html lang=\"en-us\">\n<body>\n<main>\n<article><p>{}</p></article>\n</main>\n</body>\n</html>\n",
+            article
+        );
+    }
+
+    #[test]
+    fn responses() {
+        let response = parse_response(&page("That's the right answer!  You are <em>one gold star</em> closer to restoring snow operations. <a href=\"/2023/day/6#part2\">[Continue to Part Two]</a>"));
+        assert_eq!(response.outcome, Outcome::Correct);
+        assert_eq!(response.wait, None);
+        assert_eq!(
+            response.message,
+            "That's the right answer! You are one gold star closer to restoring snow operations."
+        );
+
+        let response = parse_response(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href=\"/2023/day/6\">[Return to Day 6]</a>"));
+        assert_eq!(response.outcome, Outcome::TooHigh);
+        assert_eq!(response.wait, Some(Duration::from_secs(60)));
+
+        let response = parse_response(&page("That's not the right answer; your answer is too low.  Because you have guessed incorrectly 5 times on this puzzle, please wait 5 minutes before trying again."));
+        assert_eq!(response.outcome, Outcome::TooLow);
+        assert_eq!(response.wait, Some(Duration::from_secs(300)));
+
+        let response = parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2023/day/6\">[Return to Day 6]</a>"));
+        assert_eq!(response.outcome, Outcome::RateLimited);
+        assert_eq!(response.wait, Some(Duration::from_secs(83)));
+
+        let response = parse_response(&page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/6\">[Return to Day 6]</a>"));
+        assert_eq!(response.outcome, Outcome::AlreadySolved);
+        assert_eq!(
+            parse_response("<html></html>").outcome,
+            Outcome::Unrecognised
+        );
+    }
+
+    #[test]
+    fn log() {
+        let contents =
+            "1701846000\t1\t300\ttoo_high\t60\nnot a line\n1701846100\t1\t288\tcorrect\t0\n";
+        let history = parse_log(contents);
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history[0],
+            Submission {
+                time: 1701846000,
+                part: 1,
+                answer: "300".to_string(),
+                outcome: Outcome::TooHigh,
+                wait: 60,
+            }
+        );
+        assert_eq!(history[1].outcome, Outcome::Correct);
+    }
+
+    #[test]
+    fn refusals() {
+        let submission = |time, part, answer: &str, outcome, wait| Submission {
+            time,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            wait,
+        };
+        let history = vec![
+            submission(1000, 2, "71600", Outcome::TooHigh, 60),
+            submission(1100, 2, "70000", Outcome::TooLow, 60),
+            submission(1200, 2, "abc", Outcome::Wrong, 60),
+        ];
+        assert!(refusal(&history, 2, "71503", 1230).is_some());
+        assert_eq!(refusal(&history, 2, "71503", 1260), None);
+        assert!(refusal(&history, 2, "71600", 1260).is_some());
+        assert!(refusal(&history, 2, "80000", 1260).is_some());
+        assert!(refusal(&history, 2, "69999", 1260).is_some());
+        assert!(refusal(&history, 2, "abc", 1260).is_some());
+        assert_eq!(refusal(&history, 1, "288", 1260), None);
+
+        let history = vec![submission(1000, 1, "288", Outcome::Correct, 0)];
+        assert_eq!(
+            refusal(&history, 1, "288", 2000),
+            Some("part 1 was already solved with 288".to_string())
+        );
+    }
+}
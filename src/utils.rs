@@ -1,175 +1,530 @@
-use reqwest::StatusCode;
-
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
 use std::fs::read_to_string;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
 
-pub fn read_all(filename: &str) -> String {
-    return read_to_string(filename).expect(format!("Failed to read: {}", filename).as_str());
+// Seconds since the epoch.
+pub fn unix_time() -> u64 {
+    return std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
 }
 
-pub fn read_lines(filename: &str) -> Vec<String> {
-    return read_all(filename)
-        .lines()
-        .map(String::from)
-        .collect::<Vec<String>>();
+// The number in the module path segment with the given prefix, e.g. 17 for
+// ("aoc2023::year2023::day17", "day").
+pub fn module_number(module_path: &str, prefix: &str) -> u32 {
+    return module_path
+        .split("::")
+        .find_map(|segment| segment.strip_prefix(prefix)?.parse::<u32>().ok())
+        .unwrap();
 }
 
-macro_rules! main {
-    () => {
-        pub fn main(part: &u32, num_runs: &usize, input_file: &str) {
-            let day = std::module_path!()
-                .split("day")
-                .last()
-                .unwrap()
-                .parse::<u32>()
-                .unwrap();
-            let contents = get_contents(input_file);
-            let time_p1 = || {
-                let (result, time) = crate::utils::time_it(|| part1(&contents), num_runs);
-                let output = format!(
-                    "├────┼────────┼────────────────────────────────┼────────────┤
-│{:^4}│{:^8}│{:^32}│{:^12}│",
-                    day, 1, result, time
-                );
-                println!("{}", output);
-            };
-            let time_p2 = || {
-                let (result, time) = crate::utils::time_it(|| part2(&contents), num_runs);
-                let output = format!(
-                    "├────┼────────┼────────────────────────────────┼────────────┤
-│{:^4}│{:^8}│{:^32}│{:^12}│",
-                    day, 2, result, time
-                );
-                println!("{}", output);
-            };
-            match part {
-                1 => time_p1(),
-                2 => time_p2(),
-                _ => {
-                    time_p1();
-                    time_p2();
-                }
-            }
+pub fn read_all(filename: &str) -> std::io::Result<String> {
+    return read_to_string(filename)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", filename, e)));
+}
+
+// "-" reads standard input.
+pub fn read_input(filename: &str) -> std::io::Result<String> {
+    if filename == "-" {
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("<stdin>: {}", e)))?;
+        return Ok(contents);
+    }
+    return read_all(filename);
+}
+
+pub fn lines(input: &str) -> Vec<String> {
+    return input.lines().map(String::from).collect::<Vec<String>>();
+}
+
+macro_rules! days {
+    ($($day:ident),+ $(,)?) => {
+        $(pub mod $day;)+
+
+        pub fn solutions() -> Vec<&'static dyn crate::solution::Solution> {
+            return vec![$(&$day::Solver),+];
         }
     };
 }
 
-macro_rules! test {
-    () => {
-        #[cfg(test)]
-        mod tests {
-            use super::*;
+macro_rules! main {
+    ($title:expr) => {
+        pub struct Solver;
 
-            #[test]
-            fn part1() {
-                for input in consts::PART1_INPUTS.iter() {
-                    let inputs = get_contents(input.0);
-                    assert_eq!((input.0, super::part1(&inputs)), *input);
-                }
+        impl crate::solution::Solution for Solver {
+            fn year(&self) -> u32 {
+                return crate::utils::module_number(std::module_path!(), "year");
             }
 
-            #[test]
-            fn part2() {
-                for input in consts::PART2_INPUTS.iter() {
-                    let inputs = get_contents(input.0);
-                    assert_eq!((input.0, super::part2(&inputs)), *input);
-                }
+            fn day(&self) -> u32 {
+                return crate::utils::module_number(std::module_path!(), "day");
             }
-        }
-    };
-    (part1{
-        $($input_file1:expr => $expected1:expr),+
-    },
-    part2{
-        $($input_file2:expr => $expected2:expr),+
-    }) => {
-        #[cfg(test)]
-        mod tests {
-            use super::*;
-
-            #[test]
-            fn part1() {
-                $(
-                    let inputs = get_contents($input_file1);
-                    assert_eq!((super::part1(&inputs)), $expected1);
-                )+
+
+            fn title(&self) -> &'static str {
+                return $title;
+            }
+
+            fn parse(
+                &self,
+                input: &str,
+            ) -> Result<Box<dyn std::any::Any>, crate::parse::ParseError> {
+                return Ok(Box::new(get_contents(input)?));
+            }
+
+            fn part1(&self, input: &dyn std::any::Any) -> crate::solution::Answer {
+                return part1(input.downcast_ref().unwrap()).into();
             }
 
-            #[test]
-            fn part2() {
-                $(
-                    let inputs = get_contents($input_file2);
-                    assert_eq!((super::part2(&inputs)), $expected2);
-                )+
+            fn part2(&self, input: &dyn std::any::Any) -> crate::solution::Answer {
+                return part2(input.downcast_ref().unwrap()).into();
             }
         }
     };
 }
 
+// `span!("compress");` times the rest of the enclosing block and
+// `span!("dfs", expr)` times just `expr`. Spans nest, see `trace`.
+macro_rules! span {
+    ($name:expr) => {
+        let _span = crate::utils::Span::enter($name);
+    };
+    ($name:expr, $body:expr) => {{
+        let _span = crate::utils::Span::enter($name);
+        $body
+    }};
+}
+
+pub(crate) use days;
 pub(crate) use main;
-pub(crate) use test;
-
-pub fn time_it<T: Default, F: Fn() -> T>(f: F, num_runs: &usize) -> (T, f64) {
-    let start = std::time::Instant::now();
-    let mut result = T::default();
-    for _ in 0..*num_runs {
-        result = f();
-    }
-    let end = std::time::Instant::now();
-    return (
-        result,
-        end.duration_since(start).as_micros() as f64 / *num_runs as f64,
-    );
-}
-
-pub fn download_input(day: &u32) -> String {
-    let url = format!("https://adventofcode.com/2023/day/{}/input", day);
-    let cookie = read_all("cookies/session");
-    let cookie_map = cookie
-        .split_whitespace()
-        .map(|token| {
-            let mut tokens = token.split('=');
-            (
-                tokens.next().unwrap().to_string(),
-                tokens.next().unwrap().to_string(),
-            )
-        })
-        .collect::<HashMap<_, _>>();
-
-    let client = reqwest::blocking::Client::new();
-    let body = match client
-        .get(url)
-        .header("Cookie", format!("session={}", cookie_map["session"]))
-        .send()
-    {
-        Ok(res) => {
-            if res.status() == StatusCode::OK {
-                res.text().unwrap()
-            } else {
-                panic!("Failed to download input for day {}", day);
+pub(crate) use span;
+
+// All times are in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        // nearest-rank percentile
+        let p95 = sorted[((0.95 * n as f64).ceil() as usize).max(1) - 1];
+        let variance = sorted.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n as f64;
+        return Self {
+            runs: n,
+            mean,
+            min: sorted[0],
+            median,
+            p95,
+            stddev: variance.sqrt(),
+        };
+    }
+}
+
+pub fn format_duration(ns: f64) -> String {
+    if ns < 1e3 {
+        return format!("{:.0} ns", ns);
+    } else if ns < 1e6 {
+        return format!("{:.2} µs", ns / 1e3);
+    } else if ns < 1e9 {
+        return format!("{:.2} ms", ns / 1e6);
+    }
+    return format!("{:.2} s", ns / 1e9);
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    let bytes = bytes as f64;
+    if bytes < 1024.0 {
+        return format!("{} B", bytes);
+    } else if bytes < 1024.0 * 1024.0 {
+        return format!("{:.2} KiB", bytes / 1024.0);
+    } else if bytes < 1024.0 * 1024.0 * 1024.0 {
+        return format!("{:.2} MiB", bytes / (1024.0 * 1024.0));
+    }
+    return format!("{:.2} GiB", bytes / (1024.0 * 1024.0 * 1024.0));
+}
+
+pub fn time_it<T, F: FnMut() -> T>(mut f: F, num_runs: &usize, warmup: &usize) -> (T, Stats) {
+    for _ in 0..*warmup {
+        f();
+    }
+    let mut samples = Vec::with_capacity(*num_runs);
+    loop {
+        let start = std::time::Instant::now();
+        let result = f();
+        samples.push(start.elapsed().as_nanos() as f64);
+        if samples.len() >= *num_runs {
+            return (result, Stats::from_samples(&samples));
+        }
+    }
+}
+
+// Spans only record anything inside `trace`, on the thread that called it.
+// Everywhere else entering one costs a thread-local flag check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanTotal {
+    pub path: Vec<&'static str>,
+    pub calls: usize,
+    pub total: f64,
+}
+
+#[derive(Default)]
+struct Tracer {
+    stack: Vec<&'static str>,
+    index: HashMap<Vec<&'static str>, usize>,
+    totals: Vec<SpanTotal>,
+}
+
+thread_local! {
+    static TRACING: Cell<bool> = const { Cell::new(false) };
+    static TRACER: RefCell<Tracer> = RefCell::new(Tracer::default());
+}
+
+pub struct Span {
+    entry: Option<(usize, std::time::Instant)>,
+}
+
+impl Span {
+    pub fn enter(name: &'static str) -> Span {
+        if !TRACING.with(Cell::get) {
+            return Span { entry: None };
+        }
+        let index = TRACER.with(|tracer| {
+            let mut tracer = tracer.borrow_mut();
+            tracer.stack.push(name);
+            let path = tracer.stack.clone();
+            let next = tracer.totals.len();
+            let index = *tracer.index.entry(path.clone()).or_insert(next);
+            if index == next {
+                tracer.totals.push(SpanTotal {
+                    path,
+                    calls: 0,
+                    total: 0.0,
+                });
             }
+            index
+        });
+        return Span {
+            entry: Some((index, std::time::Instant::now())),
+        };
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some((index, start)) = self.entry else {
+            return;
+        };
+        let elapsed = start.elapsed().as_nanos() as f64;
+        TRACER.with(|tracer| {
+            let mut tracer = tracer.borrow_mut();
+            tracer.stack.pop();
+            tracer.totals[index].calls += 1;
+            tracer.totals[index].total += elapsed;
+        });
+    }
+}
+
+// Runs `f` inside a root span called `name` with tracing switched on and
+// returns every span entered on this thread, parents before their children.
+pub fn trace<T>(name: &'static str, f: impl FnOnce() -> T) -> (T, Vec<SpanTotal>) {
+    TRACER.with(|tracer| *tracer.borrow_mut() = Tracer::default());
+    TRACING.with(|tracing| tracing.set(true));
+    let result = span!(name, f());
+    TRACING.with(|tracing| tracing.set(false));
+    let totals = TRACER.with(|tracer| std::mem::take(&mut *tracer.borrow_mut()).totals);
+    return (result, totals);
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    Tree,
+    Folded,
+}
+
+fn is_child(parent: &SpanTotal, span: &SpanTotal) -> bool {
+    return span.path.len() == parent.path.len() + 1 && span.path.starts_with(&parent.path);
+}
+
+fn tree_order(spans: &[SpanTotal], parent: Option<usize>, order: &mut Vec<usize>) {
+    for (i, span) in spans.iter().enumerate() {
+        let matches = match parent {
+            Some(p) => is_child(&spans[p], span),
+            None => span.path.len() == 1,
+        };
+        if matches {
+            order.push(i);
+            tree_order(spans, Some(i), order);
         }
-        Err(e) => {
-            panic!("Failed to download input for day {}", e);
+    }
+}
+
+// Time spent in a span itself, outside of any of its children.
+fn self_time(spans: &[SpanTotal], span: &SpanTotal) -> f64 {
+    let children = spans
+        .iter()
+        .filter(|s| is_child(span, s))
+        .map(|s| s.total)
+        .sum::<f64>();
+    return (span.total - children).max(0.0);
+}
+
+// Folded stacks are one `prefix;root;child self_ns` line per span, which is
+// what flamegraph.pl and inferno read.
+pub fn format_spans(format: TraceFormat, prefix: &str, spans: &[SpanTotal]) -> String {
+    let mut order = Vec::new();
+    tree_order(spans, None, &mut order);
+    let mut out = String::new();
+    for span in order.into_iter().map(|i| &spans[i]) {
+        match format {
+            TraceFormat::Tree => {
+                let name = format!(
+                    "{}{}",
+                    "  ".repeat(span.path.len() - 1),
+                    span.path.last().unwrap()
+                );
+                let calls = if span.calls > 1 {
+                    format!("  ×{}", span.calls)
+                } else {
+                    String::new()
+                };
+                out.push_str(&format!(
+                    "{:<32}{:>12}{}\n",
+                    name,
+                    format_duration(span.total),
+                    calls
+                ));
+            }
+            TraceFormat::Folded => {
+                out.push_str(&format!(
+                    "{};{} {:.0}\n",
+                    prefix,
+                    span.path.join(";"),
+                    self_time(spans, span)
+                ));
+            }
         }
-    };
+    }
+    return out;
+}
+
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return "unknown panic".to_string();
+}
+
+// Runs `work` on up to `jobs` threads and hands the results to `emit` in the
+// same order as `items`, as soon as every earlier item is done.
+pub fn parallel_ordered<I, T, W, E>(items: &[I], jobs: usize, work: W, mut emit: E)
+where
+    I: Sync,
+    T: Send,
+    W: Fn(&I) -> T + Sync,
+    E: FnMut(T),
+{
+    if jobs <= 1 {
+        for item in items {
+            emit(work(item));
+        }
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let next = &next;
+            let work = &work;
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() || tx.send((i, work(&items[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&expected) {
+                emit(result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod module_tests {
+    #[test]
+    fn module_number() {
+        assert_eq!(super::module_number("aoc2023::year2023::day17", "day"), 17);
+        assert_eq!(
+            super::module_number("aoc2023::year2023::day17", "year"),
+            2023
+        );
+    }
+}
 
-    //    let filename = format!("inputs/day{:02}/input.txt", day);
-    //    std::fs::write(filename, body).unwrap();
-    return body;
+#[cfg(test)]
+mod time_tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(&[5.0, 1.0, 3.0, 2.0, 4.0]);
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.p95, 5.0);
+        assert_eq!(stats.stddev, 2.0f64.sqrt());
+
+        let stats = Stats::from_samples(&[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.median, 2.5);
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_duration(999.0), "999 ns");
+        assert_eq!(format_duration(1500.0), "1.50 µs");
+        assert_eq!(format_duration(2.5e6), "2.50 ms");
+        assert_eq!(format_duration(154.889e9), "154.89 s");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(5 << 30), "5.00 GiB");
+    }
+
+    #[test]
+    fn runs() {
+        let mut calls = 0;
+        let (result, stats) = time_it(
+            || {
+                calls += 1;
+                calls
+            },
+            &3,
+            &2,
+        );
+        assert_eq!(result, 5);
+        assert_eq!(stats.runs, 3);
+    }
 }
 
 #[cfg(test)]
-mod download_tests {
+mod span_tests {
     use super::*;
 
+    fn work(n: u64) -> u64 {
+        span!("work");
+        let setup = span!("setup", (0..n).sum::<u64>());
+        for _ in 0..3 {
+            span!("step");
+        }
+        return setup;
+    }
+
     #[test]
-    fn download() {
-        assert!(
-            download_input(&6).as_str()
-                == "Time:        44     82     69     81
-Distance:   202   1076   1138   1458
-",
+    fn nesting() {
+        let (result, spans) = trace("part1", || work(10));
+        assert_eq!(result, 45);
+        let paths = spans
+            .iter()
+            .map(|s| (s.path.join(";"), s.calls))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                ("part1".to_string(), 1),
+                ("part1;work".to_string(), 1),
+                ("part1;work;setup".to_string(), 1),
+                ("part1;work;step".to_string(), 3),
+            ]
         );
+        assert!(spans[0].total >= spans[1].total);
+    }
+
+    #[test]
+    fn disabled() {
+        assert_eq!(work(4), 6);
+        let (_, spans) = trace("part2", || 1);
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn formats() {
+        let span = |path: &[&'static str], calls, total| SpanTotal {
+            path: path.to_vec(),
+            calls,
+            total,
+        };
+        let spans = vec![
+            span(&["part2"], 1, 5000.0),
+            span(&["part2", "compress"], 1, 1000.0),
+            span(&["part2", "dfs"], 2, 3500.0),
+            span(&["part2", "compress", "edges"], 4, 400.0),
+        ];
+        assert_eq!(
+            format_spans(TraceFormat::Folded, "2023;day23", &spans),
+            "2023;day23;part2 500\n\
+             2023;day23;part2;compress 600\n\
+             2023;day23;part2;compress;edges 400\n\
+             2023;day23;part2;dfs 3500\n"
+        );
+        let tree = format_spans(TraceFormat::Tree, "", &spans);
+        let lines = tree.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], format!("{:<32}{:>12}", "part2", "5.00 µs"));
+        assert_eq!(lines[2], format!("{:<32}{:>12}  ×4", "    edges", "400 ns"));
+        assert_eq!(lines[3], format!("{:<32}{:>12}  ×2", "  dfs", "3.50 µs"));
+    }
+}
+
+#[cfg(test)]
+mod parallel_tests {
+    use super::*;
+
+    #[test]
+    fn ordered() {
+        let items = (0..50u64).collect::<Vec<_>>();
+        for jobs in [1, 4, 100] {
+            let mut results = Vec::new();
+            parallel_ordered(
+                &items,
+                jobs,
+                |i| {
+                    // later items finish first
+                    std::thread::sleep(std::time::Duration::from_micros(50 - i));
+                    i * i
+                },
+                |result| results.push(result),
+            );
+            assert_eq!(results, items.iter().map(|i| i * i).collect::<Vec<_>>());
+        }
     }
 }
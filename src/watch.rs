@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::report::Record;
+use crate::solution::Answer;
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Modification time and length of every file under the watched paths. A file
+// that goes missing simply drops out, which also counts as a change.
+pub type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+fn visit(path: &Path, snapshot: &mut Snapshot) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            visit(&entry.path(), snapshot);
+        }
+    } else {
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        snapshot.insert(path.to_path_buf(), (modified, metadata.len()));
+    }
+}
+
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        visit(path, &mut snapshot);
+    }
+    return snapshot;
+}
+
+// One line per part, comparing against the answers of the previous run.
+pub fn diff(previous: &[Record], current: &[Record]) -> Vec<String> {
+    let before = previous
+        .iter()
+        .map(|r| ((r.day, r.part), &r.answer))
+        .collect::<BTreeMap<_, _>>();
+    let mut lines = Vec::new();
+    for record in current {
+        let answer = if record.status.is_error() {
+            record.status.name().to_string()
+        } else {
+            record.answer.to_string()
+        };
+        let line = match before.get(&(record.day, record.part)) {
+            Some(old) if **old == record.answer && !record.status.is_error() => {
+                format!("unchanged {}", answer)
+            }
+            Some(Answer::Unsolved) | None => format!("new {}", answer),
+            Some(old) => format!("{} -> {}", old, answer),
+        };
+        lines.push(format!(
+            "day {:02} part {}: {}",
+            record.day, record.part, line
+        ));
+    }
+    return lines;
+}
+
+// Calls `run` once straight away and then again every time something under
+// `paths` changes. Only returns if `run` asks to stop.
+pub fn watch(paths: &[PathBuf], interval: Duration, mut run: impl FnMut() -> bool) {
+    let mut last = snapshot(paths);
+    if !run() {
+        return;
+    }
+    loop {
+        std::thread::sleep(interval);
+        let current = snapshot(paths);
+        if current != last {
+            last = current;
+            if !run() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use super::*;
+    use crate::answers::Status;
+
+    fn record(part: u32, answer: Answer, status: Status) -> Record {
+        return Record {
+            status,
+            ..Record::new(2023, 17, part, answer)
+        };
+    }
+
+    #[test]
+    fn answers_diff() {
+        let previous = vec![
+            record(1, Answer::Integer(102), Status::Unknown),
+            record(2, Answer::Unsolved, Status::Unknown),
+        ];
+        let current = vec![
+            record(1, Answer::Integer(102), Status::Unknown),
+            record(2, Answer::Integer(94), Status::Unknown),
+        ];
+        assert_eq!(
+            diff(&previous, &current),
+            vec!["day 17 part 1: unchanged 102", "day 17 part 2: new 94"]
+        );
+
+        let next = vec![
+            record(1, Answer::Integer(104), Status::Unknown),
+            record(2, Answer::Unsolved, Status::TimedOut),
+        ];
+        assert_eq!(
+            diff(&current, &next),
+            vec!["day 17 part 1: 102 -> 104", "day 17 part 2: 94 -> TIMEOUT"]
+        );
+    }
+
+    #[test]
+    fn snapshot_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("day17")).unwrap();
+        let file = dir.join("day17").join("test01.txt");
+        std::fs::write(&file, "2413\n").unwrap();
+        let paths = vec![dir.clone()];
+
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+        assert_eq!(snapshot(&paths), before);
+
+        std::fs::write(&file, "2413432\n").unwrap();
+        assert_ne!(snapshot(&paths), before);
+
+        std::fs::remove_file(&file).unwrap();
+        assert!(snapshot(&paths).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,7 +1,8 @@
-use crate::utils::{main, test};
+use crate::parse::ParseResult;
+use crate::utils::main;
 
-fn get_contents(filename: &str) -> Vec<String> {
-    return crate::utils::read_lines(filename);
+fn get_contents(input: &str) -> ParseResult<Vec<String>> {
+    return Ok(crate::utils::lines(input));
 }
 
 fn part1(lines: &Vec<String>) -> i32 {
@@ -127,9 +128,7 @@ fn part2(lines: &Vec<String>) -> i32 {
                         }
                     }
                 }
-                _ => {
-                    println!("Unexpected character: {}", c);
-                }
+                _ => {}
             }
         }
         if last != -1 {
@@ -141,12 +140,4 @@ fn part2(lines: &Vec<String>) -> i32 {
     return sum;
 }
 
-test!(
-    part1 {
-        "test_inputs/day01/test01.txt" => 142
-    },
-    part2 {
-        "test_inputs/day01/test02.txt" => 281
-    }
-);
-main!();
+main!("Trebuchet?!");
@@ -0,0 +1,75 @@
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+// The id of each game and every number of cubes of one colour shown in it.
+// Which draw they were shown in does not matter to either part.
+type InputData = Vec<(i32, Vec<(i32, Colour)>)>;
+
+fn get_contents(input: &str) -> ParseResult<InputData> {
+    let mut games = Vec::new();
+    for line in parse::lines(input) {
+        let (game, draws) = line.split_once(line.text, ": ")?;
+        let id = line.number::<i32>(line.strip_prefix(game, "Game ")?)?;
+        let mut cubes = Vec::new();
+        for cube in draws.split([';', ',']) {
+            let mut toks = cube.split_whitespace();
+            let num = line.number::<i32>(line.next(&mut toks, "a number of cubes")?)?;
+            let color = line.next(&mut toks, "a colour")?;
+            let color = match color {
+                "red" => Colour::Red,
+                "green" => Colour::Green,
+                "blue" => Colour::Blue,
+                _ => return Err(line.error(color, "red, green or blue")),
+            };
+            if let Some(extra) = toks.next() {
+                return Err(line.error(extra, "`,` or `;`"));
+            }
+            cubes.push((num, color));
+        }
+        games.push((id, cubes));
+    }
+    return Ok(games);
+}
+
+fn part1(games: &InputData) -> i32 {
+    let mut sum = 0;
+    for (id, cubes) in games {
+        let valid = cubes.iter().all(|(num, color)| match color {
+            Colour::Red => *num <= 12,
+            Colour::Green => *num <= 13,
+            Colour::Blue => *num <= 14,
+        });
+        if valid {
+            sum += id;
+        }
+    }
+    return sum;
+}
+
+fn part2(games: &InputData) -> i32 {
+    let mut sum = 0;
+    for (_, cubes) in games {
+        let mut blue = 0;
+        let mut green = 0;
+        let mut red = 0;
+        for (num, color) in cubes {
+            let most = match color {
+                Colour::Red => &mut red,
+                Colour::Green => &mut green,
+                Colour::Blue => &mut blue,
+            };
+            *most = (*most).max(*num);
+        }
+        sum += red * green * blue;
+    }
+    return sum;
+}
+
+main!("Cube Conundrum");
@@ -1,19 +1,11 @@
 use std::collections::HashMap;
 
 use crate::grid::Grid;
-use crate::utils::{main, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 
-fn get_contents(filename: &str) -> Grid<char> {
-    let lines = crate::utils::read_lines(filename);
-    let height = lines.len();
-    let width = lines[0].len();
-    let mut grid = Grid::new(width, height);
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c);
-        }
-    }
-    return grid;
+fn get_contents(input: &str) -> ParseResult<Grid<char>> {
+    return parse::grid(input, "a character", Some);
 }
 
 fn is_symbol(c: &char) -> bool {
@@ -164,12 +156,4 @@ fn part2(grid: &Grid<char>) -> i32 {
     return sum;
 }
 
-test!(
-    part1 {
-        "test_inputs/day03/test01.txt" => 4361
-    },
-    part2 {
-        "test_inputs/day03/test01.txt" => 467835
-    }
-);
-main!();
+main!("Gear Ratios");
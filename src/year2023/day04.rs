@@ -0,0 +1,55 @@
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
+
+fn get_contents(input: &str) -> ParseResult<Vec<(Vec<i32>, Vec<i32>)>> {
+    return parse::lines(input)
+        .iter()
+        .map(|line| {
+            let (_, cards) = line.split_once(line.text, ":")?;
+            let (winning_cards, dealt_cards) = line.split_once(cards, "|")?;
+            return Ok((
+                line.numbers(winning_cards, ' ')?,
+                line.numbers(dealt_cards, ' ')?,
+            ));
+        })
+        .collect();
+}
+
+fn part1(input: &Vec<(Vec<i32>, Vec<i32>)>) -> i32 {
+    let mut sum = 0;
+    for (winning_cards, dealt_cards) in input {
+        let mut count = 0;
+        for card in dealt_cards {
+            if winning_cards.contains(card) {
+                count += 1;
+            }
+        }
+        if count > 0 {
+            sum += 1 << (count - 1);
+        }
+    }
+    return sum;
+}
+
+fn part2(input: &Vec<(Vec<i32>, Vec<i32>)>) -> i32 {
+    let mut copies = vec![1; input.len()];
+    let mut sum = 0;
+    for (i, (winning_cards, dealt_cards)) in input.iter().enumerate() {
+        let c = copies[i];
+        sum += c;
+        let mut count = 0;
+        for card in dealt_cards {
+            if winning_cards.contains(card) {
+                count += 1;
+            }
+        }
+        if count > 0 {
+            for j in i + 1..i + 1 + count {
+                copies[j] += c;
+            }
+        }
+    }
+    return sum;
+}
+
+main!("Scratchcards");
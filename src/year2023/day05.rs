@@ -1,8 +1,9 @@
-use crate::utils::{main, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 
 fn get_contents(
-    filename: &str,
-) -> (
+    input: &str,
+) -> ParseResult<(
     Vec<i64>,
     Vec<(i64, i64, i64)>,
     Vec<(i64, i64, i64)>,
@@ -11,75 +12,43 @@ fn get_contents(
     Vec<(i64, i64, i64)>,
     Vec<(i64, i64, i64)>,
     Vec<(i64, i64, i64)>,
-) {
-    let lines = crate::utils::read_lines(filename);
-    let mut toks = lines[0].split(": ");
-    let _ = toks.next().unwrap().trim();
-    let seeds = toks
-        .next()
-        .unwrap()
-        .trim()
-        .split(' ')
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<i64>>();
-    let parse_line = |line: &String| {
-        let mut toks = line.split(" ");
-        let dst = toks.next().unwrap().trim().parse::<i64>().unwrap();
-        let src = toks.next().unwrap().trim().parse::<i64>().unwrap();
-        let len = toks.next().unwrap().trim().parse::<i64>().unwrap();
-        return (dst, src, len);
+)> {
+    let lines = parse::lines(input);
+    let Some(first) = lines.first() else {
+        return Err(parse::end_of_input(input, "`seeds: `"));
     };
+    let seeds = first.numbers(first.strip_prefix(first.text, "seeds: ")?, ' ')?;
 
-    let mut seed_to_soil_map = Vec::new();
-    let mut i = 3;
-    while lines[i] != "" {
-        seed_to_soil_map.push(parse_line(&lines[i]));
-        i += 1;
-    }
-
-    let mut soil_to_fertilizer_map = Vec::new();
-    i += 2;
-    while lines[i] != "" {
-        soil_to_fertilizer_map.push(parse_line(&lines[i]));
-        i += 1;
-    }
-
-    let mut fertilizer_to_water_map = Vec::new();
-    i += 2;
-    while lines[i] != "" {
-        fertilizer_to_water_map.push(parse_line(&lines[i]));
-        i += 1;
-    }
-
-    let mut water_to_light_map = Vec::new();
-    i += 2;
-    while lines[i] != "" {
-        water_to_light_map.push(parse_line(&lines[i]));
-        i += 1;
-    }
-
-    let mut light_to_temperature_map = Vec::new();
-    i += 2;
-    while lines[i] != "" {
-        light_to_temperature_map.push(parse_line(&lines[i]));
-        i += 1;
-    }
-
-    let mut temperature_to_humidity_map = Vec::new();
-    i += 2;
-    while lines[i] != "" {
-        temperature_to_humidity_map.push(parse_line(&lines[i]));
-        i += 1;
-    }
-
-    let mut humidity_to_location_map = Vec::new();
-    i += 2;
-    while i < lines.len() {
-        humidity_to_location_map.push(parse_line(&lines[i]));
+    // Seven maps, each a "... map:" header followed by ranges up to a blank line.
+    let mut maps = Vec::new();
+    let mut i = 1;
+    while maps.len() < 7 {
+        while i < lines.len() && lines[i].text.is_empty() {
+            i += 1;
+        }
+        let Some(header) = lines.get(i) else {
+            return Err(parse::end_of_input(input, "a map"));
+        };
+        if !header.text.ends_with(" map:") {
+            return Err(header.error(header.text, "a map header"));
+        }
         i += 1;
+        let mut map = Vec::new();
+        while i < lines.len() && !lines[i].text.is_empty() {
+            let line = lines[i];
+            let range = line.numbers::<i64>(line.text, ' ')?;
+            if range.len() != 3 {
+                return Err(line.error(line.text, "destination, source and length"));
+            }
+            map.push((range[0], range[1], range[2]));
+            i += 1;
+        }
+        maps.push(map);
     }
+    let [seed_to_soil_map, soil_to_fertilizer_map, fertilizer_to_water_map, water_to_light_map, light_to_temperature_map, temperature_to_humidity_map, humidity_to_location_map]: [Vec<(i64, i64, i64)>; 7] =
+        maps.try_into().unwrap();
 
-    return (
+    return Ok((
         seeds,
         seed_to_soil_map,
         soil_to_fertilizer_map,
@@ -88,7 +57,7 @@ fn get_contents(
         light_to_temperature_map,
         temperature_to_humidity_map,
         humidity_to_location_map,
-    );
+    ));
 }
 
 fn apply(map: &Vec<(i64, i64, i64)>, val: i64) -> i64 {
@@ -101,18 +70,6 @@ fn apply(map: &Vec<(i64, i64, i64)>, val: i64) -> i64 {
     return val;
 }
 
-fn print(map: &Vec<(i64, i64, i64)>) {
-    let mut map = map.clone();
-    map.sort_by(|a, b| a.1.cmp(&b.1));
-    let mut prev = map[0].1 + map[0].2;
-    for (_, src, len) in map.iter().skip(1) {
-        if *src != prev {
-            println!("{} {}", src, prev);
-        }
-        prev = *src + len;
-    }
-}
-
 fn part1(
     input: &(
         Vec<i64>,
@@ -135,7 +92,6 @@ fn part1(
         temperature_to_humidity_map,
         humidity_to_location_map,
     ) = input;
-    print(seed_to_soil_map);
     let soils = seeds.iter().map(|seed| apply(seed_to_soil_map, *seed));
     let fertilizers = soils.map(|soil| apply(soil_to_fertilizer_map, soil));
     let waters = fertilizers.map(|fertilizer| apply(fertilizer_to_water_map, fertilizer));
@@ -186,11 +142,4 @@ fn part2(
         .unwrap();
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 1] = [("test_inputs/day05/test01.txt", 35)];
-    pub const PART2_INPUTS: [(&str, i64); 1] = [("test_inputs/day05/test01.txt", 46)];
-}
-
-test!();
-main!();
+main!("If You Give A Seed A Fertilizer");
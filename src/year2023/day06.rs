@@ -1,19 +1,19 @@
-use crate::utils::{main, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 
-fn get_contents(filename: &str) -> (Vec<i32>, Vec<i32>) {
-    let lines = crate::utils::read_lines(filename);
-    let times = lines[0]
-        .split_whitespace()
-        .skip(1)
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect::<Vec<i32>>();
-    let distances = lines[1]
-        .split_whitespace()
-        .skip(1)
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect::<Vec<i32>>();
+fn get_contents(input: &str) -> ParseResult<(Vec<i32>, Vec<i32>)> {
+    let lines = parse::lines(input);
+    let (Some(times), Some(distances)) = (lines.first(), lines.get(1)) else {
+        return Err(parse::end_of_input(input, "`Time:` and `Distance:` lines"));
+    };
+    let time_values = times.numbers(times.strip_prefix(times.text, "Time:")?, ' ')?;
+    let distance_values =
+        distances.numbers(distances.strip_prefix(distances.text, "Distance:")?, ' ')?;
+    if time_values.len() != distance_values.len() {
+        return Err(distances.error(distances.end(), "a distance for every race"));
+    }
 
-    return (times, distances);
+    return Ok((time_values, distance_values));
 }
 
 fn calculate_num_ways(total_time: i64, distance: i64) -> i64 {
@@ -54,12 +54,4 @@ fn part2(input: &(Vec<i32>, Vec<i32>)) -> i64 {
     return calculate_num_ways(total_time, distance);
 }
 
-test!(
-    part1 {
-        "test_inputs/day06/test01.txt" => 288
-    },
-    part2 {
-        "test_inputs/day06/test01.txt" => 71503
-    }
-);
-main!();
+main!("Wait For It");
@@ -1,24 +1,24 @@
 use std::collections::HashMap;
 
-use crate::utils::{main, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 
-fn get_contents(filename: &str) -> Vec<(Hand, i32)> {
-    let lines = crate::utils::read_lines(filename);
-    return lines
+fn get_contents(input: &str) -> ParseResult<Vec<(Hand, i32)>> {
+    return parse::lines(input)
         .iter()
         .map(|line| {
+            let mut toks = line.text.split_whitespace();
+            let cards = line.next(&mut toks, "a hand")?;
+            if cards.len() != 5 || !cards.chars().all(|c| "23456789TJQKA".contains(c)) {
+                return Err(line.error(cards, "a hand of five cards"));
+            }
             let hand = Hand {
-                cards: line.split_whitespace().nth(0).unwrap().to_string(),
+                cards: cards.to_string(),
             };
-            let bid = line
-                .split_whitespace()
-                .nth(1)
-                .unwrap()
-                .parse::<i32>()
-                .unwrap();
-            return (hand, bid);
+            let bid = line.number::<i32>(line.next(&mut toks, "a bid")?)?;
+            return Ok((hand, bid));
         })
-        .collect::<Vec<_>>();
+        .collect();
 }
 
 #[derive(Debug)]
@@ -261,12 +261,4 @@ fn part2(hand_bid_pairs: &Vec<(Hand, i32)>) -> i32 {
         .sum();
 }
 
-test!(
-    part1 {
-        "test_inputs/day07/test01.txt" => 6440
-    },
-    part2 {
-        "test_inputs/day07/test01.txt" => 5905
-    }
-);
-main!();
+main!("Camel Cards");
@@ -1,25 +1,30 @@
-use crate::utils::{main, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 use std::collections::HashMap;
 
-fn get_contents(filename: &str) -> (String, HashMap<String, (String, String)>) {
-    let lines = crate::utils::read_lines(filename);
-    let directions = lines[0].clone();
-    let dir_map = lines[2..]
+fn get_contents(input: &str) -> ParseResult<(String, HashMap<String, (String, String)>)> {
+    let lines = parse::lines(input);
+    let Some(first) = lines.first() else {
+        return Err(parse::end_of_input(input, "directions"));
+    };
+    if let Some((i, c)) = first.text.char_indices().find(|(_, c)| *c != 'L' && *c != 'R') {
+        return Err(first.error(&first.text[i..i + c.len_utf8()], "`L` or `R`"));
+    }
+    let directions = first.text.to_string();
+    let dir_map = lines
         .iter()
-        .map(|s| {
-            let mut elements = s
-                .split_whitespace()
-                .map(String::from)
-                .collect::<Vec<String>>();
-            elements[2] = elements[2].replace(['(', ',', ')'], "");
-            elements[3] = elements[3].replace(['(', ',', ')'], "");
-            return (
-                elements[0].clone(),
-                (elements[2].clone(), elements[3].clone()),
-            );
+        .skip(2)
+        .map(|line| {
+            let (node, targets) = line.split_once(line.text, " = ")?;
+            let targets = line.strip_prefix(targets, "(")?;
+            let (left, right) = line.split_once(targets, ", ")?;
+            let Some(right) = right.strip_suffix(')') else {
+                return Err(line.error(line.end(), "`)`"));
+            };
+            return Ok((node.to_string(), (left.to_string(), right.to_string())));
         })
-        .collect::<HashMap<String, (String, String)>>();
-    return (directions, dir_map);
+        .collect::<ParseResult<HashMap<String, (String, String)>>>()?;
+    return Ok((directions, dir_map));
 }
 
 fn part1(input: &(String, HashMap<String, (String, String)>)) -> i64 {
@@ -90,13 +95,4 @@ fn part2(input: &(String, HashMap<String, (String, String)>)) -> i64 {
     return count;
 }
 
-test!(
-    part1 {
-        "test_inputs/day08/test01.txt" => 2,
-        "test_inputs/day08/test02.txt" => 6
-    },
-    part2 {
-        "test_inputs/day08/test03.txt" => 6
-    }
-);
-main!();
+main!("Haunted Wasteland");
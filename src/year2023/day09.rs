@@ -1,15 +1,11 @@
-use crate::utils::{main, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 
-fn get_contents(filename: &str) -> Vec<Vec<i64>> {
-    let lines = crate::utils::read_lines(filename);
-    return lines
+fn get_contents(input: &str) -> ParseResult<Vec<Vec<i64>>> {
+    return parse::lines(input)
         .iter()
-        .map(|s| {
-            s.split_whitespace()
-                .map(|x| x.parse::<i64>().unwrap())
-                .collect::<Vec<i64>>()
-        })
-        .collect::<Vec<Vec<i64>>>();
+        .map(|line| line.numbers(line.text, ' '))
+        .collect();
 }
 
 fn newton_extrapolation(y: &Vec<i64>) -> i64 {
@@ -42,12 +38,4 @@ fn part2(_input: &Vec<Vec<i64>>) -> i64 {
     return sum;
 }
 
-test!(
-    part1 {
-        "test_inputs/day09/test01.txt" => 114
-    },
-    part2 {
-        "test_inputs/day09/test01.txt" => 2
-    }
-);
-main!();
+main!("Mirage Maintenance");
@@ -1,23 +1,21 @@
 use std::collections::{HashMap, VecDeque};
 
 use crate::{
+    parse::{self, ParseResult},
     grid::Grid,
-    utils::{main, test},
+    utils::main,
 };
 
-fn get_contents(filename: &str) -> (Grid<char>, (usize, usize)) {
-    let lines = crate::utils::read_lines(filename);
-    let mut grid = Grid::new(lines[0].len(), lines.len());
-    let mut start = (0, 0);
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c);
-            if c == 'S' {
-                start = (x, y);
-            }
-        }
-    }
-    return (grid, start);
+fn get_contents(input: &str) -> ParseResult<(Grid<char>, (usize, usize))> {
+    let grid = parse::grid(input, "a pipe", |c| "|-LJ7F.S".contains(c).then_some(c))?;
+    let start = input
+        .lines()
+        .enumerate()
+        .find_map(|(y, line)| Some((line.chars().position(|c| c == 'S')?, y)));
+    let Some(start) = start else {
+        return Err(parse::end_of_input(input, "a start tile `S`"));
+    };
+    return Ok((grid, start));
 }
 
 fn cmap(c: &char) -> char {
@@ -355,12 +353,4 @@ fn part2(grid: &(Grid<char>, (usize, usize))) -> i64 {
     return interior_points.len() as i64;
 }
 
-test!(
-    part1 {
-        "test_inputs/day10/test01.txt" => 8
-    },
-    part2 {
-        "test_inputs/day10/test02.txt" => 8
-    }
-);
-main!();
+main!("Pipe Maze");
@@ -0,0 +1,211 @@
+use crate::{
+    parse::{self, ParseResult},
+    grid::Grid,
+    utils::main,
+};
+
+fn get_contents(input: &str) -> ParseResult<Grid<char>> {
+    return parse::grid(input, "`.` or `#`", |c| ".#".contains(c).then_some(c));
+}
+
+fn solve(grid: &Grid<char>, ratio: i64) -> i64 {
+    let mut galaxies = Vec::new();
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            if grid.at(x, y).unwrap() == &'#' {
+                galaxies.push((x, y));
+            }
+        }
+    }
+
+    let mut empty_x = Vec::new();
+    let mut empty_y = Vec::new();
+
+    for x in 0..grid.width {
+        let mut p = false;
+        for y in 0..grid.height {
+            if grid.at(x, y).unwrap() == &'#' {
+                p = true;
+            }
+        }
+        if !p {
+            empty_x.push(x);
+        }
+    }
+
+    for y in 0..grid.height {
+        let mut p = false;
+        for x in 0..grid.width {
+            if grid.at(x, y).unwrap() == &'#' {
+                p = true;
+            }
+        }
+        if !p {
+            empty_y.push(y);
+        }
+    }
+
+    let mut sum = 0;
+    for i in 0..galaxies.len() {
+        for j in 0..i {
+            let (x1, y1) = (galaxies[i].0 as i64, galaxies[i].1 as i64);
+            let (x2, y2) = (galaxies[j].0 as i64, galaxies[j].1 as i64);
+            let dx = x2 - x1;
+            let dy = y2 - y1;
+            let mut dist = (dx.abs() + dy.abs()) as i64;
+            let min_x = std::cmp::min(x1, x2);
+            let max_x = std::cmp::max(x1, x2);
+            for x in empty_x.iter() {
+                if *x as i64 > min_x && (max_x > *x as i64) {
+                    dist += ratio - 1;
+                }
+            }
+
+            let min_y = std::cmp::min(y1, y2);
+            let max_y = std::cmp::max(y1, y2);
+            for y in empty_y.iter() {
+                if *y as i64 > min_y && (max_y > *y as i64) {
+                    dist += ratio - 1;
+                }
+            }
+            sum += dist;
+        }
+    }
+
+    return sum;
+}
+
+fn part1(grid: &Grid<char>) -> i64 {
+    return solve(grid, 2);
+}
+
+fn part2(grid: &Grid<char>) -> i64 {
+    return solve(grid, 1_000_000);
+}
+
+main!("Cosmic Expansion");
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::property::{self, Reference, Rng};
+    use crate::solution::Answer;
+
+    // Galaxies are `true`.
+    type Image = Vec<Vec<bool>>;
+
+    fn generate(rng: &mut Rng) -> Image {
+        let (width, height) = (rng.range(1, 7), rng.range(1, 7));
+        let density = rng.range(5, 40) as u64;
+        return (0..height)
+            .map(|_| (0..width).map(|_| rng.chance(density)).collect())
+            .collect();
+    }
+
+    fn shrink(image: &Image) -> Vec<Image> {
+        let mut smaller = property::removals(image)
+            .into_iter()
+            .filter(|rows| !rows.is_empty())
+            .collect::<Vec<_>>();
+        if image[0].len() > 1 {
+            for x in 0..image[0].len() {
+                let mut narrower = image.clone();
+                narrower.iter_mut().for_each(|row| {
+                    row.remove(x);
+                });
+                smaller.push(narrower);
+            }
+        }
+        for (y, row) in image.iter().enumerate() {
+            for x in (0..row.len()).filter(|x| row[*x]) {
+                let mut fewer = image.clone();
+                fewer[y][x] = false;
+                smaller.push(fewer);
+            }
+        }
+        return smaller;
+    }
+
+    fn render(image: &Image) -> String {
+        return image
+            .iter()
+            .map(|row| {
+                let mut line = row.iter().map(|g| if *g { '#' } else { '.' }).collect::<String>();
+                line.push('\n');
+                line
+            })
+            .collect();
+    }
+
+    fn transpose(image: &Image) -> Image {
+        return (0..image[0].len())
+            .map(|x| image.iter().map(|row| row[x]).collect())
+            .collect();
+    }
+
+    fn distances(galaxies: &[(i64, i64)]) -> i64 {
+        let mut sum = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                sum += (a.0 - b.0).abs() + (a.1 - b.1).abs();
+            }
+        }
+        return sum;
+    }
+
+    // Part 1 doubles every empty row and column for real. Part 2 cannot, so
+    // it moves each galaxy by the empty rows and columns before it.
+    fn solve(image: &Image, part: u32) -> Answer {
+        if part == 1 {
+            let mut expanded = image.clone();
+            for _ in 0..2 {
+                expanded = transpose(&expanded)
+                    .into_iter()
+                    .flat_map(|row| {
+                        let copies = if row.contains(&true) { 1 } else { 2 };
+                        vec![row; copies]
+                    })
+                    .collect();
+            }
+            let mut galaxies = Vec::new();
+            for (y, row) in expanded.iter().enumerate() {
+                for (x, galaxy) in row.iter().enumerate() {
+                    if *galaxy {
+                        galaxies.push((x as i64, y as i64));
+                    }
+                }
+            }
+            return Answer::from(distances(&galaxies));
+        }
+        let empty_rows = |image: &Image| {
+            return image
+                .iter()
+                .map(|row| !row.contains(&true))
+                .collect::<Vec<_>>();
+        };
+        let (empty_y, empty_x) = (empty_rows(image), empty_rows(&transpose(image)));
+        let shift = |empty: &[bool], i: usize| {
+            return i as i64 + 999_999 * empty[..i].iter().filter(|e| **e).count() as i64;
+        };
+        let mut galaxies = Vec::new();
+        for (y, row) in image.iter().enumerate() {
+            for (x, galaxy) in row.iter().enumerate() {
+                if *galaxy {
+                    galaxies.push((shift(&empty_x, x), shift(&empty_y, y)));
+                }
+            }
+        }
+        return Answer::from(distances(&galaxies));
+    }
+
+    #[test]
+    fn matches_reference() {
+        property::check(&Reference {
+            solution: &super::Solver,
+            parts: &[1, 2],
+            generate,
+            shrink,
+            render,
+            solve,
+        });
+    }
+}
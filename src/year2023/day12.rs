@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
+
+fn get_contents(input: &str) -> ParseResult<Vec<(String, Vec<i8>)>> {
+    return parse::lines(input)
+        .iter()
+        .map(|line| {
+            let (spring_config, grouping) = line.split_once(line.text, " ")?;
+            let unknown = spring_config.char_indices().find(|(_, c)| !"?.#".contains(*c));
+            if let Some((i, c)) = unknown {
+                return Err(line.error(&spring_config[i..i + c.len_utf8()], "`?`, `.` or `#`"));
+            }
+            return Ok((spring_config.to_string(), line.numbers(grouping, ',')?));
+        })
+        .collect();
+}
+
+// flag: previous character was a '#'
+fn solve(
+    config: &str,
+    ii: usize,
+    grouping: &[i8],
+    flag: bool,
+    dp: &mut HashMap<(usize, Vec<i8>, bool), i64>,
+) -> i64 {
+    if grouping.len() == 0 {
+        if config[ii..].find('#').is_some() {
+            return 0;
+        } else {
+            return 1;
+        }
+    }
+    let mut mem_or_solve = |i: usize, grouping: &[i8], flag: bool| {
+        if let Some(n_solns) = dp.get(&(i, grouping.to_vec(), flag)) {
+            *n_solns
+        } else {
+            let n_solns = solve(config, i, grouping, flag, dp);
+            dp.insert((i, grouping.to_vec(), flag), n_solns);
+            n_solns
+        }
+    };
+
+    let mut j = 0;
+    let mut grouping = grouping.to_vec();
+    let mut p = flag;
+    for (i, c) in config.chars().enumerate().skip(ii) {
+        if c == '#' {
+            if j >= grouping.len() {
+                return 0;
+            }
+            grouping[j] -= 1;
+            if grouping[j] < 0 {
+                return 0;
+            }
+            if !p {
+                p = true;
+            }
+        } else if c == '.' {
+            if p {
+                if grouping[j] > 0 {
+                    return 0;
+                }
+                j += 1;
+                p = false;
+            }
+        } else if c == '?' {
+            let total0 = if p {
+                if grouping[j] > 0 {
+                    0
+                } else {
+                    mem_or_solve(i + 1, &grouping[j + 1..], false)
+                }
+            } else {
+                mem_or_solve(i + 1, &grouping[j..], false)
+            };
+
+            if j >= grouping.len() {
+                return total0;
+            } else {
+                grouping[j] -= 1;
+                let total1 = if grouping[j] < 0 {
+                    0
+                } else {
+                    mem_or_solve(i + 1, &grouping[j..], true)
+                };
+                return total0 + total1;
+            }
+        }
+    }
+    if grouping.iter().all(|&x| x == 0) {
+        return 1;
+    }
+    return 0;
+}
+
+fn part1(problem: &Vec<(String, Vec<i8>)>) -> i64 {
+    let mut total = 0;
+    for (spring_config, grouping) in problem {
+        let solution = solve(spring_config, 0, grouping, false, &mut HashMap::new());
+        total += solution;
+    }
+    return total;
+}
+
+fn part2(problem: &Vec<(String, Vec<i8>)>) -> i64 {
+    let mut total = 0;
+    for (spring_config, grouping) in problem {
+        let spring_config = vec![spring_config.clone(); 5].join("?");
+        let grouping = grouping.repeat(5);
+        let mut dp = HashMap::new();
+        let solution = solve(&spring_config, 0, &grouping, false, &mut dp);
+        total += solution;
+    }
+
+    return total;
+}
+
+main!("Hot Springs");
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::property::{self, Reference, Rng};
+    use crate::solution::Answer;
+
+    type Record = (Vec<char>, Vec<usize>);
+
+    fn groups(springs: &[char]) -> Vec<usize> {
+        return springs
+            .split(|c| *c != '#')
+            .map(|run| run.len())
+            .filter(|n| *n > 0)
+            .collect();
+    }
+
+    fn generate(rng: &mut Rng) -> Vec<Record> {
+        return (0..rng.range(1, 3))
+            .map(|_| {
+                let unknown = rng.range(0, 60) as u64;
+                let springs = (0..rng.range(1, 7))
+                    .map(|_| {
+                        if rng.chance(unknown) {
+                            '?'
+                        } else {
+                            rng.pick(&['.', '#'])
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                // Mostly records that have at least one arrangement.
+                let mut expected = if rng.chance(70) {
+                    let filled = springs
+                        .iter()
+                        .map(|c| if *c == '?' { rng.pick(&['.', '#']) } else { *c })
+                        .collect::<Vec<_>>();
+                    groups(&filled)
+                } else {
+                    (0..rng.range(1, 3))
+                        .map(|_| rng.range(1, 3) as usize)
+                        .collect()
+                };
+                if expected.is_empty() {
+                    expected.push(1);
+                }
+                (springs, expected)
+            })
+            .collect();
+    }
+
+    fn shrink(records: &Vec<Record>) -> Vec<Vec<Record>> {
+        let mut smaller = property::removals(records)
+            .into_iter()
+            .filter(|rest| !rest.is_empty())
+            .collect::<Vec<_>>();
+        for (i, (springs, expected)) in records.iter().enumerate() {
+            let mut change = |record: Record| {
+                let mut changed = records.clone();
+                changed[i] = record;
+                smaller.push(changed);
+            };
+            for shorter in property::removals(springs) {
+                if !shorter.is_empty() {
+                    change((shorter, expected.clone()));
+                }
+            }
+            for fewer in property::removals(expected) {
+                if !fewer.is_empty() {
+                    change((springs.clone(), fewer));
+                }
+            }
+            for (j, c) in springs.iter().enumerate().filter(|(_, c)| **c != '.') {
+                let mut simpler = springs.clone();
+                simpler[j] = if *c == '?' { '#' } else { '.' };
+                change((simpler, expected.clone()));
+            }
+        }
+        return smaller;
+    }
+
+    fn render(records: &Vec<Record>) -> String {
+        return records
+            .iter()
+            .map(|(springs, expected)| {
+                let expected = expected.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                format!(
+                    "{} {}\n",
+                    springs.iter().collect::<String>(),
+                    expected.join(",")
+                )
+            })
+            .collect();
+    }
+
+    // Every way of filling in the `?`s, checked one by one.
+    fn every_arrangement(springs: &[char], expected: &[usize]) -> i64 {
+        let unknown = springs.iter().filter(|c| **c == '?').count();
+        let mut count = 0;
+        for mask in 0..1u32 << unknown {
+            let mut bit = 0;
+            let filled = springs
+                .iter()
+                .map(|c| {
+                    if *c != '?' {
+                        return *c;
+                    }
+                    bit += 1;
+                    return if mask >> (bit - 1) & 1 == 1 { '#' } else { '.' };
+                })
+                .collect::<Vec<_>>();
+            if groups(&filled) == expected {
+                count += 1;
+            }
+        }
+        return count;
+    }
+
+    // Fills in the springs left to right like `every_arrangement`, but gives
+    // up on a prefix as soon as its groups stop matching. Unfolded records
+    // have too many `?`s to try them all.
+    fn walk(springs: &[char], expected: &[usize], done: usize, run: usize) -> i64 {
+        if run > 0 && (done == expected.len() || run > expected[done]) {
+            return 0;
+        }
+        let Some(c) = springs.first() else {
+            let done = match run {
+                0 => done,
+                _ if run == expected[done] => done + 1,
+                _ => return 0,
+            };
+            return (done == expected.len()) as i64;
+        };
+        let mut count = 0;
+        if *c != '.' {
+            count += walk(&springs[1..], expected, done, run + 1);
+        }
+        if *c != '#' {
+            count += match run {
+                0 => walk(&springs[1..], expected, done, 0),
+                _ if run == expected[done] => walk(&springs[1..], expected, done + 1, 0),
+                _ => 0,
+            };
+        }
+        return count;
+    }
+
+    fn solve(records: &Vec<Record>, part: u32) -> Answer {
+        let mut total = 0;
+        for (springs, expected) in records {
+            total += if part == 1 {
+                every_arrangement(springs, expected)
+            } else {
+                let mut unfolded = springs.clone();
+                for _ in 0..4 {
+                    unfolded.push('?');
+                    unfolded.extend(springs);
+                }
+                walk(&unfolded, &expected.repeat(5), 0, 0)
+            };
+        }
+        return Answer::from(total);
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_eq!(every_arrangement(&"?###????????".chars().collect::<Vec<_>>(), &[3, 2, 1]), 10);
+        assert_eq!(walk(&"?###????????".chars().collect::<Vec<_>>(), &[3, 2, 1], 0, 0), 10);
+        property::check(&Reference {
+            solution: &super::Solver,
+            parts: &[1, 2],
+            generate,
+            shrink,
+            render,
+            solve,
+        });
+    }
+}
@@ -1,26 +1,29 @@
 use crate::{
+    parse::{self, ParseResult},
     grid::Grid,
-    utils::{main, test},
+    utils::main,
 };
 
-fn get_contents(filename: &str) -> Vec<(Grid<char>, Grid<char>)> {
-    let lines = crate::utils::read_lines(filename);
+fn get_contents(input: &str) -> ParseResult<Vec<(Grid<char>, Grid<char>)>> {
+    let lines = parse::lines(input);
     return lines
-        .split(|line| line.is_empty())
+        .split(|line| line.text.is_empty())
+        .filter(|lines| !lines.is_empty())
         .map(|lines| {
             let height = lines.len();
-            let width = lines[0].len();
+            let width = lines[0].text.chars().count();
             let mut gridh = Grid::new(width, height);
             let mut gridv = Grid::new(height, width);
             for (y, line) in lines.iter().enumerate() {
-                for (x, c) in line.chars().enumerate() {
+                let row = parse::row(line, width, "`.` or `#`", |c| ".#".contains(c).then_some(c))?;
+                for (x, c) in row.into_iter().enumerate() {
                     gridh.set(x, y, c);
                     gridv.set(y, x, c);
                 }
             }
-            return (gridh, gridv);
+            return Ok((gridh, gridv));
         })
-        .collect::<Vec<_>>();
+        .collect();
 }
 
 fn reflection_count(grid: &Grid<char>) -> i64 {
@@ -86,17 +89,9 @@ fn part2(grids: &Vec<(Grid<char>, Grid<char>)>) -> i64 {
     for (gridh, gridv) in grids {
         let counth = reflection_count_smudge(gridh);
         let countv = reflection_count_smudge(gridv);
-        println!("{} {}", counth, countv);
         sum += 100 * counth + countv;
     }
     return sum as i64;
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Point of Incidence");
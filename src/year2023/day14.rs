@@ -1,17 +1,11 @@
 use crate::{
+    parse::{self, ParseResult},
     grid::Grid,
-    utils::{main, test},
+    utils::main,
 };
 
-fn get_contents(filename: &str) -> Grid<char> {
-    let lines = crate::utils::read_lines(filename);
-    let mut grid = Grid::new(lines[0].len(), lines.len());
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c);
-        }
-    }
-    return grid;
+fn get_contents(input: &str) -> ParseResult<Grid<char>> {
+    return parse::grid(input, "`O`, `#` or `.`", |c| "O#.".contains(c).then_some(c));
 }
 
 fn calc_load(grid: &Grid<char>) -> i64 {
@@ -155,11 +149,4 @@ fn part2(grid: &Grid<char>) -> i64 {
     return calc_load(&prev[(1_000_000_000 - n_runs - 1) % prev.len()]);
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Parabolic Reflector Dish");
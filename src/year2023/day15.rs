@@ -0,0 +1,90 @@
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operation {
+    Remove,
+    Insert(usize),
+}
+
+// `text` is the whole step, which is what part 1 hashes.
+#[derive(Debug, Clone)]
+struct Step {
+    text: String,
+    label: String,
+    operation: Operation,
+}
+
+fn get_contents(input: &str) -> ParseResult<Vec<Step>> {
+    let mut steps = Vec::new();
+    for line in parse::lines(input) {
+        for step in line.text.split(',').filter(|step| !step.is_empty()) {
+            let label = step.trim_end_matches(|c: char| c.is_ascii_digit() || c == '=' || c == '-');
+            let operation = match &step[label.len()..] {
+                "-" => Some(Operation::Remove),
+                operation => operation
+                    .strip_prefix('=')
+                    .and_then(|focal_length| focal_length.parse().ok())
+                    .map(Operation::Insert),
+            };
+            let Some(operation) = operation.filter(|_| !label.is_empty()) else {
+                return Err(line.error(step, "a step like `rn=1` or `cm-`"));
+            };
+            steps.push(Step {
+                text: step.to_string(),
+                label: label.to_string(),
+                operation,
+            });
+        }
+    }
+    return Ok(steps);
+}
+
+fn elf_hash(string: &str) -> usize {
+    let mut hash = 0;
+    for c in string.chars() {
+        let cval = c as usize;
+        hash += cval;
+        hash *= 17;
+        hash %= 256;
+    }
+    return hash;
+}
+
+fn part1(steps: &Vec<Step>) -> usize {
+    return steps.iter().map(|step| elf_hash(&step.text)).sum::<usize>();
+}
+
+fn part2(steps: &Vec<Step>) -> usize {
+    let mut boxes = vec![Vec::<(&str, usize)>::new(); 256];
+    for step in steps.iter() {
+        let key = step.label.as_str();
+        let bucket = &mut boxes[elf_hash(key)];
+        match step.operation {
+            Operation::Insert(value) => {
+                if let Some((_, v)) = bucket.iter_mut().find(|(k, _)| *k == key) {
+                    *v = value;
+                } else {
+                    bucket.push((key, value));
+                }
+            }
+            Operation::Remove => {
+                if let Some(pos) = bucket.iter().position(|(k, _)| *k == key) {
+                    bucket.remove(pos);
+                }
+            }
+        }
+    }
+    return boxes
+        .iter()
+        .enumerate()
+        .map(|(bx, v)| {
+            v.iter()
+                .enumerate()
+                .map(|(slt, (_, val))| val * (slt + 1) * (bx + 1))
+                .sum::<usize>()
+        })
+        .sum::<usize>();
+}
+
+main!("Lens Library");
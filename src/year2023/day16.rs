@@ -1,19 +1,13 @@
 use std::{cmp::max, collections::VecDeque};
 
 use crate::{
+    parse::{self, ParseResult},
     grid::Grid,
-    utils::{main, test},
+    utils::main,
 };
 
-fn get_contents(filename: &str) -> Grid<char> {
-    let lines = crate::utils::read_lines(filename);
-    let mut grid = Grid::new(lines.len(), lines[0].len());
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c);
-        }
-    }
-    return grid;
+fn get_contents(input: &str) -> ParseResult<Grid<char>> {
+    return parse::grid(input, "a mirror or `.`", |c| ".|-/\\".contains(c).then_some(c));
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -249,11 +243,4 @@ fn part2(grid: &Grid<char>) -> i64 {
     return max(max(right_max, left_max), max(top_max, bottom_max));
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("The Floor Will Be Lava");
@@ -1,18 +1,12 @@
 use crate::{
+    parse::{self, ParseResult},
     algos::priority_queue,
     grid::Grid,
-    utils::{main, read_lines, test},
+    utils::main,
 };
 
-fn get_contents(filename: &str) -> Grid<i64> {
-    let lines = read_lines(filename);
-    let mut grid = Grid::new(lines[0].len(), lines.len());
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c.to_string().parse::<i64>().unwrap());
-        }
-    }
-    return grid;
+fn get_contents(input: &str) -> ParseResult<Grid<i64>> {
+    return parse::grid(input, "a digit", |c| c.to_digit(10).map(|d| d as i64));
 }
 
 const DIRECTIONS: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
@@ -188,11 +182,4 @@ fn part2(grid: &Grid<i64>) -> i64 {
         .unwrap()
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Clumsy Crucible");
@@ -0,0 +1,396 @@
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
+
+fn get_contents(input: &str) -> ParseResult<Vec<(char, i64, String)>> {
+    parse::lines(input)
+        .iter()
+        .map(|line| {
+            let mut toks = line.text.split_whitespace();
+            let direction = line.next(&mut toks, "a direction")?;
+            let c = match direction {
+                "U" | "D" | "L" | "R" => direction.chars().next().unwrap(),
+                _ => return Err(line.error(direction, "`U`, `D`, `L` or `R`")),
+            };
+            let dist = line.number::<i64>(line.next(&mut toks, "a distance")?)?;
+            let colour = line.next(&mut toks, "a colour")?;
+            let hex = colour.strip_prefix("(#").and_then(|c| c.strip_suffix(')'));
+            let hex = match hex {
+                Some(hex) if hex.len() == 6 && hex.chars().all(|c| "0123456789abcdef".contains(c)) => {
+                    hex
+                }
+                _ => return Err(line.error(colour, "a colour like `(#70c710)`")),
+            };
+            // Part 2 reads the last digit as the direction.
+            if !"0123".contains(&hex[5..]) {
+                return Err(line.error(&hex[5..], "a direction digit `0`, `1`, `2` or `3`"));
+            }
+            Ok((c, dist, colour[1..colour.len() - 1].to_string()))
+        })
+        .collect()
+}
+
+fn part1(plan: &Vec<(char, i64, String)>) -> i64 {
+    let mut area = 0;
+    let mut xcurr = 0;
+    let mut ycurr = 0;
+    let mut x_min = 0;
+    let mut x_max = 0;
+    let mut y_min = 0;
+    let mut y_max = 0;
+    let mut num_points = 0;
+    for (c, dist, _) in plan.iter() {
+        let instr = match c {
+            'R' => 0,
+            'D' => 1,
+            'L' => 2,
+            'U' => 3,
+            _ => panic!("invalid direction"),
+        };
+        let dist = *dist;
+        num_points += dist;
+        xcurr += match instr {
+            0 => dist,
+            1 => 0,
+            2 => -dist,
+            3 => 0,
+            _ => panic!("invalid direction"),
+        };
+        ycurr += match instr {
+            0 => 0,
+            1 => dist,
+            2 => 0,
+            3 => -dist,
+            _ => panic!("invalid direction"),
+        };
+        x_min = x_min.min(xcurr);
+        x_max = x_max.max(xcurr);
+        y_min = y_min.min(ycurr);
+        y_max = y_max.max(ycurr);
+
+        let (dx, dy) = match instr {
+            0 => (dist, 0),
+            1 => (0, dist),
+            2 => (-dist, 0),
+            3 => (0, -dist),
+            _ => panic!("invalid direction"),
+        };
+        // green's theorem (div by 2 in the end to avoid precision issues)
+        area += -dx * ycurr + dy * xcurr;
+    }
+    // the sign of the area depends on which way round the plan goes
+    (area.abs() + num_points) / 2 + 1
+}
+
+fn parse_hex(s: &str) -> i64 {
+    let mut n = 0;
+    for c in s.chars() {
+        n *= 16;
+        n += match c {
+            '0'..='9' => c as i64 - '0' as i64,
+            'a'..='f' => c as i64 - 'a' as i64 + 10,
+            _ => panic!("invalid hex"),
+        };
+    }
+    n
+}
+
+fn part2(plan: &Vec<(char, i64, String)>) -> i64 {
+    let mut area = 0;
+
+    let mut xcurr = 0;
+    let mut ycurr = 0;
+
+    let mut x_min = 0;
+    let mut x_max = 0;
+
+    let mut y_min = 0;
+    let mut y_max = 0;
+    let mut num_points = 0;
+
+    for (_, _, code) in plan.iter() {
+        let instr = code
+            .chars()
+            .last()
+            .unwrap()
+            .to_string()
+            .parse::<i64>()
+            .unwrap();
+        let dist = parse_hex(&code[1..code.len() - 1]);
+        num_points += dist;
+        xcurr += match instr {
+            0 => dist,
+            1 => 0,
+            2 => -dist,
+            3 => 0,
+            _ => panic!("invalid direction"),
+        };
+        ycurr += match instr {
+            0 => 0,
+            1 => dist,
+            2 => 0,
+            3 => -dist,
+            _ => panic!("invalid direction"),
+        };
+        x_min = x_min.min(xcurr);
+        x_max = x_max.max(xcurr);
+        y_min = y_min.min(ycurr);
+        y_max = y_max.max(ycurr);
+
+        let (dx, dy) = match instr {
+            0 => (dist, 0),
+            1 => (0, dist),
+            2 => (-dist, 0),
+            3 => (0, -dist),
+            _ => panic!("invalid direction"),
+        };
+        area += -dx * ycurr + dy * xcurr;
+    }
+    (area.abs() + num_points) / 2 + 1
+}
+
+main!("Lavaduct Lagoon");
+
+#[cfg(test)]
+mod reference_tests {
+    use std::collections::HashSet;
+
+    use crate::property::{self, Reference, Rng};
+    use crate::solution::Answer;
+
+    const SIZE: i64 = 4;
+
+    // The trench goes around the outline of `cells`, with the columns and
+    // rows of the outline stretched by `widths` and `heights`, one pair for
+    // each part. The plan starts at corner `start` and goes round
+    // anticlockwise if `reverse` is set.
+    #[derive(Clone)]
+    struct Lagoon {
+        cells: Vec<(i64, i64)>,
+        widths: [Vec<i64>; 2],
+        heights: [Vec<i64>; 2],
+        start: usize,
+        reverse: bool,
+    }
+
+    fn connected(cells: &HashSet<(i64, i64)>, from: (i64, i64)) -> HashSet<(i64, i64)> {
+        let mut seen = HashSet::from([from]);
+        let mut stack = vec![from];
+        while let Some((x, y)) = stack.pop() {
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if cells.contains(&next) && seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        return seen;
+    }
+
+    // One piece with no holes, and no two cells touching only at a corner,
+    // so the outline is a single loop that never touches itself.
+    fn valid(cells: &[(i64, i64)]) -> bool {
+        let Some(first) = cells.first() else {
+            return false;
+        };
+        let set = cells.iter().copied().collect::<HashSet<_>>();
+        if connected(&set, *first).len() != set.len() {
+            return false;
+        }
+        let outside = (-1..=SIZE)
+            .flat_map(|x| (-1..=SIZE).map(move |y| (x, y)))
+            .filter(|cell| !set.contains(cell))
+            .collect::<HashSet<_>>();
+        if connected(&outside, (-1, -1)).len() != outside.len() {
+            return false;
+        }
+        return (-1..SIZE).all(|x| {
+            (-1..SIZE).all(|y| {
+                let a = set.contains(&(x, y));
+                let b = set.contains(&(x + 1, y + 1));
+                let c = set.contains(&(x + 1, y));
+                let d = set.contains(&(x, y + 1));
+                !(a == b && c == d && a != c)
+            })
+        });
+    }
+
+    fn generate(rng: &mut Rng) -> Lagoon {
+        let mut cells = vec![(rng.range(0, SIZE - 1), rng.range(0, SIZE - 1))];
+        for _ in 0..rng.range(0, 8) {
+            let (x, y) = rng.pick(&cells);
+            let next = rng.pick(&[(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+            let mut grown = cells.clone();
+            grown.push(next);
+            let inside = (0..SIZE).contains(&next.0) && (0..SIZE).contains(&next.1);
+            if inside && !cells.contains(&next) && valid(&grown) {
+                cells = grown;
+            }
+        }
+        let mut stretch = |most| (0..SIZE).map(|_| rng.range(1, most)).collect::<Vec<_>>();
+        return Lagoon {
+            widths: [stretch(3), stretch(9)],
+            heights: [stretch(3), stretch(9)],
+            start: rng.below(4 * cells.len()),
+            reverse: rng.chance(50),
+            cells,
+        };
+    }
+
+    fn shrink(lagoon: &Lagoon) -> Vec<Lagoon> {
+        let mut smaller = Vec::new();
+        for cells in property::removals(&lagoon.cells) {
+            if valid(&cells) {
+                smaller.push(Lagoon {
+                    cells,
+                    ..lagoon.clone()
+                });
+            }
+        }
+        for part in 0..2 {
+            for i in 0..SIZE as usize {
+                if lagoon.widths[part][i] > 1 {
+                    let mut narrower = lagoon.clone();
+                    narrower.widths[part][i] = 1;
+                    smaller.push(narrower);
+                }
+                if lagoon.heights[part][i] > 1 {
+                    let mut shorter = lagoon.clone();
+                    shorter.heights[part][i] = 1;
+                    smaller.push(shorter);
+                }
+            }
+        }
+        if lagoon.start > 0 {
+            smaller.push(Lagoon {
+                start: 0,
+                ..lagoon.clone()
+            });
+        }
+        return smaller;
+    }
+
+    // The corners of the outline in order, going clockwise.
+    fn outline(cells: &[(i64, i64)]) -> Vec<(i64, i64)> {
+        let set = cells.iter().copied().collect::<HashSet<_>>();
+        let mut edges = Vec::new();
+        for &(x, y) in cells {
+            if !set.contains(&(x, y - 1)) {
+                edges.push(((x, y), (x + 1, y)));
+            }
+            if !set.contains(&(x + 1, y)) {
+                edges.push(((x + 1, y), (x + 1, y + 1)));
+            }
+            if !set.contains(&(x, y + 1)) {
+                edges.push(((x + 1, y + 1), (x, y + 1)));
+            }
+            if !set.contains(&(x - 1, y)) {
+                edges.push(((x, y + 1), (x, y)));
+            }
+        }
+        let mut corners = vec![edges[0].0];
+        while corners.len() < edges.len() {
+            let from = corners[corners.len() - 1];
+            corners.push(edges.iter().find(|(a, _)| *a == from).unwrap().1);
+        }
+        return corners;
+    }
+
+    // The corners the plan visits for `part`, starting and ending at the
+    // same one.
+    fn path(lagoon: &Lagoon, part: usize) -> Vec<(i64, i64)> {
+        let mut corners = outline(&lagoon.cells);
+        if lagoon.reverse {
+            corners.reverse();
+        }
+        let start = lagoon.start % corners.len();
+        corners.rotate_left(start);
+        corners.push(corners[0]);
+        let offset = |sizes: &Vec<i64>, i| sizes[..i as usize].iter().sum::<i64>();
+        return corners
+            .iter()
+            .map(|&(x, y)| {
+                (
+                    offset(&lagoon.widths[part], x),
+                    offset(&lagoon.heights[part], y),
+                )
+            })
+            .collect();
+    }
+
+    // Straight runs of the path as a direction and a distance.
+    fn steps(path: &[(i64, i64)]) -> Vec<(usize, i64)> {
+        let mut steps: Vec<(usize, i64)> = Vec::new();
+        for pair in path.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            let direction = match ((x1 - x0).signum(), (y1 - y0).signum()) {
+                (1, _) => 0,
+                (_, 1) => 1,
+                (-1, _) => 2,
+                _ => 3,
+            };
+            let distance = (x1 - x0).abs() + (y1 - y0).abs();
+            match steps.last_mut() {
+                Some((last, total)) if *last == direction => *total += distance,
+                _ => steps.push((direction, distance)),
+            }
+        }
+        // The path may start halfway along a side.
+        if steps.len() > 1 && steps[0].0 == steps[steps.len() - 1].0 {
+            let (_, distance) = steps.pop().unwrap();
+            steps[0].1 += distance;
+        }
+        return steps;
+    }
+
+    fn render(lagoon: &Lagoon) -> String {
+        let first = steps(&path(lagoon, 0));
+        let second = steps(&path(lagoon, 1));
+        return first
+            .iter()
+            .zip(second.iter())
+            .map(|((direction, distance), (_, hex))| {
+                format!(
+                    "{} {} (#{:05x}{})\n",
+                    ['R', 'D', 'L', 'U'][*direction],
+                    distance,
+                    hex,
+                    direction
+                )
+            })
+            .collect();
+    }
+
+    // Digs the trench one square at a time and counts the squares that
+    // cannot be reached from outside it.
+    fn solve(lagoon: &Lagoon, part: u32) -> Answer {
+        let path = path(lagoon, part as usize - 1);
+        let mut trench = HashSet::new();
+        for pair in path.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            for x in x0.min(x1)..=x0.max(x1) {
+                for y in y0.min(y1)..=y0.max(y1) {
+                    trench.insert((x, y));
+                }
+            }
+        }
+        let width = path.iter().map(|p| p.0).max().unwrap() + 1;
+        let height = path.iter().map(|p| p.1).max().unwrap() + 1;
+        let open = (-1..=width)
+            .flat_map(|x| (-1..=height).map(move |y| (x, y)))
+            .filter(|p| !trench.contains(p))
+            .collect::<HashSet<_>>();
+        let outside = connected(&open, (-1, -1)).len() as i64;
+        return Answer::from((width + 2) * (height + 2) - outside);
+    }
+
+    #[test]
+    fn matches_reference() {
+        property::check(&Reference {
+            solution: &super::Solver,
+            parts: &[1, 2],
+            generate,
+            shrink,
+            render,
+            solve,
+        });
+    }
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fmt::Display};
 
-use crate::utils::{main, read_lines, test};
+use crate::parse::{self, Line, ParseResult};
+use crate::utils::main;
 
 #[derive(Debug)]
 struct Part {
@@ -16,27 +17,29 @@ impl Part {
     }
 }
 
-impl From<String> for Part {
-    fn from(value: String) -> Self {
-        let value = value[1..value.len() - 1].to_string();
+impl Part {
+    fn parse(line: &Line) -> ParseResult<Self> {
+        let value = line.strip_prefix(line.text, "{")?;
+        let Some(value) = value.strip_suffix('}') else {
+            return Err(line.error(line.end(), "`}`"));
+        };
         let toks = value.split(',');
         let mut a = 0;
         let mut m = 0;
         let mut s = 0;
         let mut x = 0;
         for tok in toks {
-            let prop_val = tok.split('=').collect::<Vec<&str>>();
-            let prop = prop_val[0];
-            let val = prop_val[1].parse::<i64>().unwrap();
+            let (prop, val) = line.split_once(tok, "=")?;
+            let val = line.number::<i64>(val)?;
             match prop {
                 "a" => a = val,
                 "m" => m = val,
                 "s" => s = val,
                 "x" => x = val,
-                _ => panic!("Unknown property {}", prop),
+                _ => return Err(line.error(prop, "one of x, m, a, s")),
             }
         }
-        Self::new(a, m, s, x)
+        Ok(Self::new(a, m, s, x))
     }
 }
 
@@ -57,21 +60,15 @@ impl Workflow {
     }
 }
 
-impl From<String> for Workflow {
-    fn from(value: String) -> Self {
-        let mut name = String::new();
+impl Workflow {
+    fn parse(line: &Line) -> ParseResult<Self> {
+        let (name, rule_toks) = line.split_once(line.text, "{")?;
+        let Some(rule_toks) = rule_toks.strip_suffix('}') else {
+            return Err(line.error(line.end(), "`}`"));
+        };
         let mut rules = Vec::new();
-        let mut i = 0;
-        for c in value.chars() {
-            if c == '{' {
-                break;
-            }
-            name.push(c);
-            i += 1;
-        }
-        let rule_toks = value[i + 1..value.len() - 1].split(',');
-        for tok in rule_toks {
-            if !tok.contains(':') {
+        for tok in rule_toks.split(',') {
+            let Some((cond, action)) = tok.split_once(':') else {
                 rules.push(Rule {
                     prop: char::default(),
                     cond_type: char::default(),
@@ -80,13 +77,17 @@ impl From<String> for Workflow {
                     neg: false,
                 });
                 continue;
-            }
-            let rule = tok.split(':').collect::<Vec<&str>>();
-            let cond = rule[0];
-            let action = rule[1];
-            let prop = cond.chars().nth(0).unwrap();
-            let cond_type = cond.chars().nth(1).unwrap();
-            let cond_val = cond[2..cond.len()].parse::<i64>().unwrap();
+            };
+            let mut chars = cond.chars();
+            let prop = match chars.next() {
+                Some(c @ ('x' | 'm' | 'a' | 's')) => c,
+                _ => return Err(line.error(cond, "one of x, m, a, s")),
+            };
+            let cond_type = match chars.next() {
+                Some(c @ ('<' | '>')) => c,
+                _ => return Err(line.error(&cond[1..], "`<` or `>`")),
+            };
+            let cond_val = line.number::<i64>(&cond[2..])?;
             rules.push(Rule {
                 prop,
                 cond_type,
@@ -95,7 +96,10 @@ impl From<String> for Workflow {
                 neg: false,
             });
         }
-        Self { rules, name }
+        Ok(Self {
+            rules,
+            name: name.to_string(),
+        })
     }
 }
 
@@ -198,22 +202,22 @@ impl Rule {
     }
 }
 
-fn get_contents(filename: &str) -> (Vec<Workflow>, Vec<Part>) {
-    let lines = read_lines(filename);
+fn get_contents(input: &str) -> ParseResult<(Vec<Workflow>, Vec<Part>)> {
+    let lines = parse::lines(input);
     let mut workflows = Vec::new();
     let mut i = 0;
     for line in lines.iter() {
-        if line == "" {
+        if line.text == "" {
             break;
         }
-        workflows.push(Workflow::from(line.clone()));
+        workflows.push(Workflow::parse(line)?);
         i += 1;
     }
     let mut parts = Vec::new();
-    for line in lines[i + 1..].iter() {
-        parts.push(Part::from(line.to_string()));
+    for line in lines.iter().skip(i + 1) {
+        parts.push(Part::parse(line)?);
     }
-    return (workflows, parts);
+    return Ok((workflows, parts));
 }
 
 fn part1((workflows, parts): &(Vec<Workflow>, Vec<Part>)) -> i64 {
@@ -465,13 +469,4 @@ fn part2((workflows, _): &(Vec<Workflow>, Vec<Part>)) -> i64 {
     )
 }
 
-test!(
-    part1 {
-        "test_inputs/day19/test01.txt" => 19114
-    },
-    part2 {
-        "test_inputs/day19/test01.txt" => 167409079868000
-    }
-
-);
-main!();
+main!("Aplenty");
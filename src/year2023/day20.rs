@@ -1,11 +1,12 @@
 use std::collections::{HashMap, VecDeque};
 
 use crate::{
+    parse::{self, ParseResult},
     algos::{
         graph::{self, AdjacencyList, Graph},
         lcm,
     },
-    utils::{main, read_lines, test},
+    utils::{main, span},
 };
 use std::io::{self, Write};
 
@@ -63,60 +64,65 @@ fn press_button<F: FnMut(usize, bool, usize) -> bool>(
     n: i64,
     mut func: F,
 ) {
-    let mut modules = modules.clone();
-    let modules_to_node = graph
-        .nodes
-        .iter()
-        .enumerate()
-        .map(|(i, n)| (n.clone(), i))
-        .collect::<HashMap<_, _>>();
-    let start_node = modules_to_node.get("broadcaster").unwrap();
+    span!("press_button");
+    let (mut modules, start_node, mut processing, order) = span!("setup", {
+        let mut modules = modules.clone();
+        let modules_to_node = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.clone(), i))
+            .collect::<HashMap<_, _>>();
+        let start_node = *modules_to_node.get("broadcaster").unwrap();
 
-    let mut in_degree = vec![0; graph.nodes.len()];
-    for edges in graph.edges.iter() {
-        for (neighbor, _) in edges.iter() {
-            in_degree[*neighbor] += 1;
+        let mut in_degree = vec![0; graph.nodes.len()];
+        for edges in graph.edges.iter() {
+            for (neighbor, _) in edges.iter() {
+                in_degree[*neighbor] += 1;
+            }
         }
-    }
 
-    let mut processing = vec![false; graph.nodes.len()];
-    let mut order = HashMap::<usize, Vec<usize>>::new();
-    for node_id in 0..graph.nodes.len() {
-        let Some(module) = modules.get_mut(&graph.nodes[node_id]) else {
-            continue;
-        };
-        let Module::Conjunction(conjunction) = module else {
-            continue;
-        };
-        let in_degree = in_degree[node_id];
-        conjunction.saved_states = vec![false; in_degree];
-    }
-
-    for node_id0 in 0..graph.nodes.len() {
-        for node_id1 in 0..graph.nodes.len() {
-            let Some(module) = modules.get_mut(&graph.nodes[node_id1]) else {
+        let processing = vec![false; graph.nodes.len()];
+        let mut order = HashMap::<usize, Vec<usize>>::new();
+        for node_id in 0..graph.nodes.len() {
+            let Some(module) = modules.get_mut(&graph.nodes[node_id]) else {
                 continue;
             };
-            let Module::Conjunction(_) = module else {
+            let Module::Conjunction(conjunction) = module else {
                 continue;
             };
-            if graph.edges[node_id0].iter().any(|(n, _)| *n == node_id1) {
-                if let Some(ord) = order.get_mut(&node_id1) {
-                    ord.push(node_id0);
-                } else {
-                    order.insert(node_id1, vec![node_id0]);
+            let in_degree = in_degree[node_id];
+            conjunction.saved_states = vec![false; in_degree];
+        }
+
+        for node_id0 in 0..graph.nodes.len() {
+            for node_id1 in 0..graph.nodes.len() {
+                let Some(module) = modules.get_mut(&graph.nodes[node_id1]) else {
+                    continue;
+                };
+                let Module::Conjunction(_) = module else {
+                    continue;
                 };
+                if graph.edges[node_id0].iter().any(|(n, _)| *n == node_id1) {
+                    if let Some(ord) = order.get_mut(&node_id1) {
+                        ord.push(node_id0);
+                    } else {
+                        order.insert(node_id1, vec![node_id0]);
+                    };
+                }
             }
         }
-    }
+        (modules, start_node, processing, order)
+    });
 
+    span!("simulate");
     let mut queue = VecDeque::new();
     let mut button_presses = 0;
     loop {
         button_presses += 1;
         io::stdout().flush().unwrap();
-        for (neighbor, _) in graph.edges[*start_node].iter() {
-            queue.push_back((*start_node, *neighbor, false));
+        for (neighbor, _) in graph.edges[start_node].iter() {
+            queue.push_back((start_node, *neighbor, false));
             processing[*neighbor] = true;
         }
 
@@ -166,16 +172,14 @@ fn press_button<F: FnMut(usize, bool, usize) -> bool>(
     }
 }
 
-fn get_contents(filename: &str) -> InputData {
+fn get_contents(input: &str) -> ParseResult<InputData> {
     let mut graph = graph::AdjacencyList::new();
-    let lines = read_lines(filename);
+    let lines = parse::lines(input);
     let mut modules = HashMap::new();
     for line in lines.iter() {
-        let mut toks = line.split("->");
-        let module = toks.next().unwrap().trim();
-        let destination = toks
-            .next()
-            .unwrap()
+        let (module, destination) = line.split_once(line.text, "->")?;
+        let module = module.trim();
+        let destination = destination
             .split(", ")
             .map(|s| s.trim())
             .collect::<Vec<_>>();
@@ -186,7 +190,7 @@ fn get_contents(filename: &str) -> InputData {
         } else if module.starts_with("%") {
             (module[1..].trim(), Module::FlipFlop(FlipFlop::new()))
         } else {
-            panic!("Unknown module type: {}", module);
+            return Err(line.error(module, "`broadcaster`, `%name` or `&name`"));
         };
 
         modules.insert(name.to_string(), mod_type);
@@ -204,7 +208,7 @@ fn get_contents(filename: &str) -> InputData {
             graph.add_weighted_edge(src, dst, ());
         }
     }
-    return (graph, modules);
+    return Ok((graph, modules));
 }
 
 fn part1((graph, modules): &InputData) -> i64 {
@@ -286,11 +290,4 @@ fn part2((graph, modules): &InputData) -> i64 {
         .fold(1, lcm)
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Pulse Propagation");
@@ -1,26 +1,24 @@
 use std::{collections::HashSet, mem::swap};
 
 use crate::{
+    parse::{self, ParseResult},
     grid::Grid,
-    utils::{main, read_lines, test},
+    utils::main,
 };
 
 type InputData = (Grid<char>, (usize, usize));
 
-fn get_contents(filename: &str) -> InputData {
-    let lines = read_lines(filename);
-    let mut start = (0, 0);
-    let mut grid = Grid::new(lines[0].len(), lines.len());
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c);
-            if c == 'S' {
-                start = (x, y);
-                grid.set(x, y, '.');
-            }
-        }
-    }
-    return (grid, start);
+fn get_contents(input: &str) -> ParseResult<InputData> {
+    let mut grid = parse::grid(input, "`.`, `#` or `S`", |c| ".#S".contains(c).then_some(c))?;
+    let start = input
+        .lines()
+        .enumerate()
+        .find_map(|(y, line)| Some((line.chars().position(|c| c == 'S')?, y)));
+    let Some(start) = start else {
+        return Err(parse::end_of_input(input, "a start tile `S`"));
+    };
+    grid.set(start.0, start.1, '.');
+    return Ok((grid, start));
 }
 
 fn get_neighbours(x: isize, y: isize) -> [(isize, isize); 4] {
@@ -98,8 +96,7 @@ fn part2((grid, start): &InputData) -> i64 {
                         next.insert(neighbour.clone());
                     }
                 } else {
-                    println!("{} {}", x, y);
-                    panic!();
+                    panic!("({}, {}) is outside the grid", x, y);
                 }
             }
         }
@@ -150,11 +147,4 @@ fn part2((grid, start): &InputData) -> i64 {
     return a * k * k + b * k + c;
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Step Counter");
@@ -1,8 +1,9 @@
 use std::collections::{HashSet, VecDeque};
 
 use crate::{
+    parse::{self, ParseResult},
     grid::Grid,
-    utils::{main, read_lines, test},
+    utils::main,
 };
 
 #[derive(Clone, Debug)]
@@ -13,27 +14,22 @@ struct Brick {
 
 type InputData = Vec<Brick>;
 
-fn get_contents(filename: &str) -> InputData {
-    read_lines(filename)
+fn get_contents(input: &str) -> ParseResult<InputData> {
+    parse::lines(input)
         .iter()
         .map(|line| {
-            let mut parts = line.split('~');
-            let from = parts
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|s| s.parse::<isize>().unwrap())
-                .collect::<Vec<_>>();
-            let to = parts
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|s| s.parse::<isize>().unwrap())
-                .collect::<Vec<_>>();
-            Brick {
-                from: (from[0], from[1], from[2]),
-                to: (to[0], to[1], to[2]),
-            }
+            let (from, to) = line.split_once(line.text, "~")?;
+            let point = |s: &str| {
+                let coordinates = line.numbers::<isize>(s, ',')?;
+                if coordinates.len() != 3 {
+                    return Err(line.error(s, "three coordinates"));
+                }
+                Ok((coordinates[0], coordinates[1], coordinates[2]))
+            };
+            Ok(Brick {
+                from: point(from)?,
+                to: point(to)?,
+            })
         })
         .collect()
 }
@@ -176,11 +172,130 @@ fn part2(bricks: &InputData) -> i64 {
     affects.iter().sum::<usize>() as i64
 }
 
+main!("Sand Slabs");
+
 #[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
+mod reference_tests {
+    use std::collections::HashSet;
+
+    use crate::property::{self, Reference, Rng};
+    use crate::solution::Answer;
+
+    type Point = (i64, i64, i64);
+    type Slab = (Point, Point);
+
+    fn cubes(slab: &Slab) -> Vec<Point> {
+        let ((x0, y0, z0), (x1, y1, z1)) = *slab;
+        return (x0..=x1)
+            .flat_map(|x| (y0..=y1).flat_map(move |y| (z0..=z1).map(move |z| (x, y, z))))
+            .collect();
+    }
+
+    fn overlaps(slab: &Slab, others: &[Slab]) -> bool {
+        let taken = others.iter().flat_map(cubes).collect::<HashSet<_>>();
+        return cubes(slab).iter().any(|cube| taken.contains(cube));
+    }
+
+    fn lowered(slab: &Slab) -> Slab {
+        let ((x0, y0, z0), (x1, y1, z1)) = *slab;
+        return ((x0, y0, z0 - 1), (x1, y1, z1 - 1));
+    }
+
+    // Moves every slab down one step at a time until none can move.
+    fn settle(slabs: &[Slab]) -> Vec<Slab> {
+        let mut slabs = slabs.to_vec();
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for i in 0..slabs.len() {
+                let below = lowered(&slabs[i]);
+                let mut others = slabs.clone();
+                others.remove(i);
+                if (below.0).2 >= 1 && !overlaps(&below, &others) {
+                    slabs[i] = below;
+                    moved = true;
+                }
+            }
+        }
+        return slabs;
+    }
 
-test!();
-main!();
+    fn generate(rng: &mut Rng) -> Vec<Slab> {
+        let mut slabs = Vec::new();
+        for _ in 0..rng.range(1, 7) {
+            let (x, y, z) = (rng.range(0, 3), rng.range(0, 3), rng.range(1, 8));
+            let length = rng.range(0, 2);
+            let end = match rng.below(3) {
+                0 => ((x + length).min(3), y, z),
+                1 => (x, (y + length).min(3), z),
+                _ => (x, y, z + length),
+            };
+            let slab = ((x, y, z), end);
+            if !overlaps(&slab, &slabs) {
+                slabs.push(slab);
+            }
+        }
+        return slabs;
+    }
+
+    fn shrink(slabs: &Vec<Slab>) -> Vec<Vec<Slab>> {
+        let mut smaller = property::removals(slabs)
+            .into_iter()
+            .filter(|rest| !rest.is_empty())
+            .collect::<Vec<_>>();
+        for i in 0..slabs.len() {
+            let below = lowered(&slabs[i]);
+            let mut others = slabs.clone();
+            others.remove(i);
+            if (below.0).2 >= 1 && !overlaps(&below, &others) {
+                let mut lower = slabs.clone();
+                lower[i] = below;
+                smaller.push(lower);
+            }
+        }
+        return smaller;
+    }
+
+    fn render(slabs: &Vec<Slab>) -> String {
+        return slabs
+            .iter()
+            .map(|((x0, y0, z0), (x1, y1, z1))| {
+                format!("{},{},{}~{},{},{}\n", x0, y0, z0, x1, y1, z1)
+            })
+            .collect();
+    }
+
+    // Takes each slab out of the settled stack in turn and lets the rest
+    // settle again, counting the slabs that moved.
+    fn solve(slabs: &Vec<Slab>, part: u32) -> Answer {
+        let settled = settle(slabs);
+        let mut safe = 0;
+        let mut fallen = 0;
+        for i in 0..settled.len() {
+            let mut rest = settled.clone();
+            rest.remove(i);
+            let moved = settle(&rest)
+                .iter()
+                .zip(rest.iter())
+                .filter(|(after, before)| after != before)
+                .count();
+            if moved == 0 {
+                safe += 1;
+            }
+            fallen += moved;
+        }
+        return Answer::from(if part == 1 { safe } else { fallen });
+    }
+
+    #[test]
+    fn matches_reference() {
+        property::check(&Reference {
+            solution: &super::Solver,
+            parts: &[1, 2],
+            generate,
+            shrink,
+            render,
+            solve,
+        });
+    }
+}
@@ -1,22 +1,18 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
+    parse::{self, ParseResult},
     algos::priority_queue::BinaryHeap,
     grid,
-    utils::{main, read_lines, test},
+    utils::{main, span},
 };
 
 type InputData = grid::Grid<char>;
 
-fn get_contents(filename: &str) -> InputData {
-    let lines = read_lines(filename);
-    let mut grid = grid::Grid::new(lines[0].len(), lines.len());
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.set(x, y, c);
-        }
-    }
-    return grid;
+fn get_contents(input: &str) -> ParseResult<InputData> {
+    return parse::grid(input, "a path, forest or slope", |c| {
+        "#.^>v<".contains(c).then_some(c)
+    });
 }
 
 fn find_start(grid: &InputData) -> (usize, usize) {
@@ -134,7 +130,7 @@ fn part2(grid: &InputData) -> i64 {
     let mut nodes = HashSet::new();
     let mut edges = HashMap::new();
 
-    for x in 0..grid.width {
+    span!("graph", for x in 0..grid.width {
         for y in 0..grid.height {
             if grid.at(x, y).unwrap() == &'#' {
                 continue;
@@ -148,7 +144,7 @@ fn part2(grid: &InputData) -> i64 {
                     .insert(((nx, ny), 1));
             }
         }
-    }
+    });
 
     // small graph
     let mut new_nodes = nodes
@@ -162,7 +158,7 @@ fn part2(grid: &InputData) -> i64 {
 
     let mut new_edges = HashMap::new();
 
-    for n in new_nodes.iter() {
+    span!("compress", for n in new_nodes.iter() {
         let mut visited = HashSet::new();
         let mut stack = Vec::new();
         stack.push((n.0, n.1, 0));
@@ -186,16 +182,12 @@ fn part2(grid: &InputData) -> i64 {
                 stack.push((nx, ny, dist + 1));
             }
         }
-    }
-
-    dfs(grid, &new_edges, start, end, &mut HashSet::new(), 0)
-}
+    });
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
+    span!(
+        "dfs",
+        dfs(grid, &new_edges, start, end, &mut HashSet::new(), 0)
+    )
 }
 
-test!();
-main!();
+main!("A Long Walk");
@@ -1,4 +1,5 @@
-use crate::utils::{main, read_lines, test};
+use crate::parse::{self, Line, ParseResult};
+use crate::utils::main;
 
 type InputData = Vec<HailStone>;
 
@@ -98,26 +99,25 @@ impl HailStone {
     }
 }
 
-impl From<&str> for Point {
-    fn from(s: &str) -> Self {
-        let s = s.trim();
-        let mut iter = s.split(',');
-        let x = iter.next().unwrap().trim().parse::<i64>().unwrap();
-        let y = iter.next().unwrap().trim().parse::<i64>().unwrap();
-        let z = iter.next().unwrap().trim().parse::<i64>().unwrap();
-        Self::new(x, y, z)
+impl Point {
+    fn parse(line: &Line, s: &str) -> ParseResult<Self> {
+        let coordinates = line.numbers::<i64>(s, ',')?;
+        if coordinates.len() != 3 {
+            return Err(line.error(s, "three coordinates"));
+        }
+        Ok(Self::new(coordinates[0], coordinates[1], coordinates[2]))
     }
 }
 
-fn get_contents(filename: &str) -> InputData {
-    let lines = read_lines(filename);
-    lines
+fn get_contents(input: &str) -> ParseResult<InputData> {
+    parse::lines(input)
         .iter()
-        .map(|s| {
-            let mut iter = s.split("@");
-            let point_str = iter.next().unwrap().trim();
-            let vel_str = iter.next().unwrap().trim();
-            HailStone::new(point_str.into(), vel_str.into())
+        .map(|line| {
+            let (point_str, vel_str) = line.split_once(line.text, "@")?;
+            Ok(HailStone::new(
+                Point::parse(line, point_str.trim())?,
+                Point::parse(line, vel_str.trim())?,
+            ))
         })
         .collect()
 }
@@ -302,11 +302,4 @@ fn part2(hail_stones: &InputData) -> i64 {
     0
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Never Tell Me The Odds");
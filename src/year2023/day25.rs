@@ -1,26 +1,26 @@
-use crate::utils::{main, read_lines, test};
+use crate::parse::{self, ParseResult};
+use crate::utils::main;
 
 use crate::algos::priority_queue::BinaryHeap;
+use crate::solution::Answer;
 use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 
 type InputData = HashMap<String, HashSet<String>>;
 
-fn get_contents(filename: &str) -> InputData {
-    let lines = read_lines(filename);
+fn get_contents(input: &str) -> ParseResult<InputData> {
+    let lines = parse::lines(input);
     let mut connections = lines
         .iter()
         .map(|line| {
-            let mut toks = line.split(":");
-            let name = toks.next().unwrap().to_string();
+            let (name, children_str) = line.split_once(line.text, ":")?;
             let mut nbr = HashSet::new();
-            let children_str = toks.next().unwrap().trim();
             for nbr_name in children_str.split_whitespace() {
                 nbr.insert(nbr_name.trim().to_string());
             }
-            (name, nbr)
+            Ok((name.to_string(), nbr))
         })
-        .collect::<HashMap<_, _>>();
+        .collect::<ParseResult<HashMap<_, _>>>()?;
     for name in connections.keys().cloned().collect::<Vec<_>>() {
         let nbr = connections.get(&name).unwrap().clone();
         for nbr_name in nbr.iter() {
@@ -30,7 +30,7 @@ fn get_contents(filename: &str) -> InputData {
                 .insert(name.clone());
         }
     }
-    return connections;
+    return Ok(connections);
 }
 
 fn merge_nodes(
@@ -171,15 +171,8 @@ fn part1(connections: &InputData) -> i64 {
     stoer_wagner(connections) as i64
 }
 
-fn part2(_input_file: &InputData) -> i64 {
-    0
+fn part2(_input_file: &InputData) -> Answer {
+    Answer::Unsolved
 }
 
-#[cfg(test)]
-mod consts {
-    pub const PART1_INPUTS: [(&str, i64); 0] = [];
-    pub const PART2_INPUTS: [(&str, i64); 0] = [];
-}
-
-test!();
-main!();
+main!("Snowverload");
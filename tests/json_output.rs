@@ -0,0 +1,241 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Runs every example through `aoc2023 run --format json` and parses the
+// output back, so anything a solver prints to stdout breaks the test.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        let Json::Object(fields) = self else {
+            return None;
+        };
+        return fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, expected: &str) -> String {
+        return format!("expected {} at byte {}", expected, self.pos);
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.text[self.pos..].starts_with(token.as_bytes()) {
+            self.pos += token.len();
+            return true;
+        }
+        return false;
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        if self.eat("null") {
+            return Ok(Json::Null);
+        }
+        if self.eat("true") {
+            return Ok(Json::Bool(true));
+        }
+        if self.eat("false") {
+            return Ok(Json::Bool(false));
+        }
+        if self.eat("[") {
+            let mut items = Vec::new();
+            if self.eat("]") {
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(self.value()?);
+                if self.eat("]") {
+                    return Ok(Json::Array(items));
+                }
+                if !self.eat(",") {
+                    return Err(self.error("`,` or `]`"));
+                }
+            }
+        }
+        if self.eat("{") {
+            let mut fields = Vec::new();
+            if self.eat("}") {
+                return Ok(Json::Object(fields));
+            }
+            loop {
+                self.skip_whitespace();
+                let key = self.string()?;
+                if !self.eat(":") {
+                    return Err(self.error("`:`"));
+                }
+                fields.push((key, self.value()?));
+                if self.eat("}") {
+                    return Ok(Json::Object(fields));
+                }
+                if !self.eat(",") {
+                    return Err(self.error("`,` or `}`"));
+                }
+            }
+        }
+        if self.text.get(self.pos) == Some(&b'"') {
+            return Ok(Json::String(self.string()?));
+        }
+        let start = self.pos;
+        while self.pos < self.text.len() && b"+-.eE0123456789".contains(&self.text[self.pos]) {
+            self.pos += 1;
+        }
+        let number = std::str::from_utf8(&self.text[start..self.pos]).unwrap();
+        return match number.parse() {
+            Ok(n) => Ok(Json::Number(n)),
+            Err(_) => Err(self.error("a value")),
+        };
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.text.get(self.pos) != Some(&b'"') {
+            return Err(self.error("a string"));
+        }
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.text.get(self.pos) {
+                None => return Err(self.error("`\"`")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.pos += 1;
+                    match self.text.get(self.pos) {
+                        Some(b'n') => bytes.push(b'\n'),
+                        Some(b'r') => bytes.push(b'\r'),
+                        Some(b't') => bytes.push(b'\t'),
+                        Some(b'u') => {
+                            let hex = self.text.get(self.pos + 1..self.pos + 5);
+                            let code = hex
+                                .and_then(|hex| std::str::from_utf8(hex).ok())
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("four hex digits"))?;
+                            bytes.extend(code.to_string().bytes());
+                            self.pos += 4;
+                        }
+                        Some(c @ (b'"' | b'\\' | b'/')) => bytes.push(*c),
+                        _ => return Err(self.error("an escape")),
+                    }
+                }
+                Some(c) if *c < 0x20 => return Err(self.error("no control characters")),
+                Some(c) => bytes.push(*c),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        return String::from_utf8(bytes).map_err(|e| e.to_string());
+    }
+}
+
+fn parse(text: &str) -> Result<Json, String> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != text.len() {
+        return Err(parser.error("the end of the output"));
+    }
+    return Ok(value);
+}
+
+#[test]
+fn parser() {
+    assert_eq!(
+        parse(r#" [{"a": -1.5e3, "b\n": [null, true]}] "#),
+        Ok(Json::Array(vec![Json::Object(vec![
+            ("a".to_string(), Json::Number(-1500.0)),
+            (
+                "b\n".to_string(),
+                Json::Array(vec![Json::Null, Json::Bool(true)])
+            ),
+        ])]))
+    );
+    assert!(parse("[1 2]").is_err());
+    assert!(parse("3 0\n[]").is_err());
+}
+
+// The entries of `dir` named `<prefix><number>`, with their number.
+fn numbered(dir: &Path, prefix: &str) -> Vec<(u32, PathBuf)> {
+    return std::fs::read_dir(dir)
+        .unwrap()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let number = name.strip_prefix(prefix)?.parse().ok()?;
+            return Some((number, entry.path()));
+        })
+        .collect();
+}
+
+// test_inputs/YYYY/dayNN/*.txt
+fn examples() -> Vec<(u32, u32, PathBuf)> {
+    let mut examples = Vec::new();
+    for (year, dir) in numbered(Path::new("test_inputs"), "") {
+        for (day, dir) in numbered(&dir, "day") {
+            for file in std::fs::read_dir(dir).unwrap().flatten() {
+                if file.path().extension().is_some_and(|ext| ext == "txt") {
+                    examples.push((year, day, file.path()));
+                }
+            }
+        }
+    }
+    examples.sort();
+    return examples;
+}
+
+#[test]
+fn json_for_every_example() {
+    let examples = examples();
+    assert!(!examples.is_empty());
+    for (year, day, path) in examples {
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+            .args(["run", "--format", "json"])
+            .args(["--year", &year.to_string(), "--days", &day.to_string()])
+            .arg("--input")
+            .arg(&path)
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let records = match parse(&stdout) {
+            Ok(Json::Array(records)) => records,
+            Ok(other) => panic!("{}: not an array: {:?}", path.display(), other),
+            Err(error) => panic!("{}: {}\n{}", path.display(), error, stdout),
+        };
+        let parts = records
+            .iter()
+            .map(|record| {
+                assert_eq!(record.get("day"), Some(&Json::Number(day as f64)));
+                return record.get("part").cloned();
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parts,
+            [Some(Json::Number(1.0)), Some(Json::Number(2.0))],
+            "{}",
+            path.display()
+        );
+    }
+}